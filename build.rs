@@ -0,0 +1,63 @@
+//! Generates the registry of solutions for the registry binary, `src/bin/registry/main.rs`.
+//!
+//! Every solution binary in `src/bin` named `<year>_<day>.rs` is compiled into the registry binary as a
+//! module, so that `all` and `time` can run it in-process. See `template::registry`.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+fn is_solution_name(name: &str) -> bool {
+    let mut parts = name.split('_');
+
+    matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(year), Some(day), None)
+            if year.len() == 4
+                && day.len() == 2
+                && year.bytes().all(|b| b.is_ascii_digit())
+                && day.bytes().all(|b| b.is_ascii_digit())
+    )
+}
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .filter(|name| is_solution_name(name))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+
+    let mut code = String::from("// @generated by build.rs\n\n");
+
+    for name in &names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        writeln!(code, "#[allow(dead_code)]").unwrap();
+        writeln!(code, "#[path = {:?}]", path.to_string_lossy()).unwrap();
+        writeln!(code, "mod solution_{name};\n").unwrap();
+    }
+
+    writeln!(
+        code,
+        "pub const SOLUTIONS: &[advent_of_code::template::registry::Entry] = &[{}];",
+        names
+            .iter()
+            .map(|name| format!("solution_{name}::SOLUTION"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+    .unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), code).unwrap();
+}
@@ -1,13 +1,26 @@
 use itertools::Itertools;
 use std::str::FromStr;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2024, 1, parse = parse);
 
 // Disclaimer - I am making extensive use of improper practices for production code, for example the
 // liberal use of `.unwrap()`.
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut left, mut right) = unzip_lines(input);
+pub fn parse(input: &str) -> (Vec<u32>, Vec<u32>) {
+    input
+        .lines()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .collect_tuple::<(&str, &str)>()
+                .unwrap()
+        })
+        .map(|(left, right)| (u32::from_str(left).unwrap(), u32::from_str(right).unwrap()))
+        .unzip()
+}
+
+pub fn part_one((left, right): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
+    let mut left = left.clone();
+    let mut right = right.clone();
 
     left.sort_unstable();
     right.sort_unstable();
@@ -21,10 +34,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (left, right) = unzip_lines(input);
-
-    let popularity = right.into_iter().counts();
+pub fn part_two((left, right): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
+    let popularity = right.iter().counts();
 
     let result = left
         .iter()
@@ -36,31 +47,23 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(result)
 }
 
-fn unzip_lines(input: &str) -> (Vec<u32>, Vec<u32>) {
-    input
-        .lines()
-        .map(|line| {
-            line.split_ascii_whitespace()
-                .collect_tuple::<(&str, &str)>()
-                .unwrap()
-        })
-        .map(|(left, right)| (u32::from_str(left).unwrap(), u32::from_str(right).unwrap()))
-        .unzip()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(31));
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(2024, 3);
 
 /*
     # Performance Optimisation
@@ -63,14 +63,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(161));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(48));
     }
@@ -1,7 +1,7 @@
 use num_traits::FromPrimitive;
 use strum::EnumIter;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2024, 4);
 
 /*
     # Performance Optimisation
@@ -274,13 +274,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(9));
     }
 }
@@ -2,7 +2,7 @@ use itertools::Itertools;
 use std::cmp::Ordering;
 use std::str::FromStr;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2024, 5);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (page_ordering_str, page_numbers_str) = input.split_once("\n\n").unwrap();
@@ -98,13 +98,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(123));
     }
 }
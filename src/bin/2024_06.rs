@@ -5,10 +5,31 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::thread;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2024, 6);
 
-const MAX_X_LENGTH: usize = if !cfg!(test) { 130 } else { 10 };
-const MAX_Y_LENGTH: usize = if !cfg!(test) { 130 } else { 10 };
+/// The width and height of the largest map, the puzzle input.
+const MAX_LENGTH: usize = 130;
+
+/// The width and height of the map, which may be smaller than [`MAX_LENGTH`], e.g. for the examples.
+#[derive(Copy, Clone, Debug)]
+struct Bounds {
+    width: usize,
+    height: usize,
+}
+
+impl Bounds {
+    /// The bounds of the map of an input, `None` if the map is empty.
+    fn of(input: &str) -> Option<Self> {
+        let width = input.lines().next().map_or(0, str::len);
+        let height = input.lines().count();
+
+        (width > 0 && height > 0).then_some(Self { width, height })
+    }
+
+    fn contains(self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+}
 
 #[derive(Copy, Clone, Debug)]
 enum Heading {
@@ -41,7 +62,11 @@ impl Position {
         Self { x, y, heading }
     }
 
-    fn advance(self, obstacles: &[[bool; MAX_Y_LENGTH]; MAX_X_LENGTH]) -> Option<(Self, bool)> {
+    fn advance(
+        self,
+        obstacles: &[[bool; MAX_LENGTH]; MAX_LENGTH],
+        bounds: Bounds,
+    ) -> Option<(Self, bool)> {
         let (x, y) = match self.heading {
             Heading::North => (self.x, self.y.checked_sub(1)?),
             Heading::East => (self.x + 1, self.y),
@@ -49,7 +74,7 @@ impl Position {
             Heading::West => (self.x.checked_sub(1)?, self.y),
         };
 
-        if x >= MAX_X_LENGTH || y >= MAX_Y_LENGTH {
+        if !bounds.contains(x, y) {
             return None;
         }
 
@@ -74,7 +99,11 @@ impl Position {
         }
     }
 
-    fn advance_pt2(self, obstacles: &mut [Vec<Option<Obstacle>>]) -> Option<(Self, bool)> {
+    fn advance_pt2(
+        self,
+        obstacles: &mut [Vec<Option<Obstacle>>],
+        bounds: Bounds,
+    ) -> Option<(Self, bool)> {
         let (x, y) = match self.heading {
             Heading::North => (self.x, self.y.checked_sub(1)?),
             Heading::East => (self.x + 1, self.y),
@@ -82,7 +111,7 @@ impl Position {
             Heading::West => (self.x.checked_sub(1)?, self.y),
         };
 
-        if x >= MAX_X_LENGTH || y >= MAX_Y_LENGTH {
+        if !bounds.contains(x, y) {
             return None;
         }
 
@@ -113,7 +142,8 @@ impl Position {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut obstacles = [[false; MAX_Y_LENGTH]; MAX_X_LENGTH];
+    let bounds = Bounds::of(input)?;
+    let mut obstacles = [[false; MAX_LENGTH]; MAX_LENGTH];
     let mut start_position: Option<Position> = None;
 
     input
@@ -131,12 +161,12 @@ pub fn part_one(input: &str) -> Option<u32> {
         });
 
     let mut current_position = start_position.unwrap();
-    let mut visited_positions = [[false; MAX_Y_LENGTH]; MAX_X_LENGTH];
+    let mut visited_positions = [[false; MAX_LENGTH]; MAX_LENGTH];
     let mut visited_positions_sum: u32 = 1;
 
     visited_positions[current_position.y][current_position.x] = true;
 
-    while let Some((new_position, did_advance)) = current_position.advance(&obstacles) {
+    while let Some((new_position, did_advance)) = current_position.advance(&obstacles, bounds) {
         current_position = new_position;
 
         if did_advance && !visited_positions[new_position.y][new_position.x] {
@@ -197,8 +227,9 @@ impl Obstacle {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut obstacles = vec![vec![None; MAX_X_LENGTH]; MAX_Y_LENGTH];
-    let mut obstacles2 = [[false; MAX_Y_LENGTH]; MAX_X_LENGTH];
+    let bounds = Bounds::of(input)?;
+    let mut obstacles = vec![vec![None; bounds.width]; bounds.height];
+    let mut obstacles2 = [[false; MAX_LENGTH]; MAX_LENGTH];
     let mut start_position: Option<Position> = None;
 
     input
@@ -219,7 +250,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let mut possible_positions = HashSet::new();
     let mut current_position = start_position.unwrap();
 
-    while let Some((new_position, did_advance)) = current_position.advance(&obstacles2) {
+    while let Some((new_position, did_advance)) = current_position.advance(&obstacles2, bounds) {
         current_position = new_position;
 
         if did_advance {
@@ -228,9 +259,9 @@ pub fn part_two(input: &str) -> Option<u32> {
     }
 
     let loops = Arc::new(AtomicU32::new(0));
-    let mut handles = Vec::with_capacity(MAX_Y_LENGTH * MAX_X_LENGTH);
+    let mut handles = Vec::with_capacity(bounds.height);
 
-    for chunk in &possible_positions.into_iter().chunks(MAX_X_LENGTH) {
+    for chunk in &possible_positions.into_iter().chunks(bounds.width) {
         let obstacles = obstacles.clone();
         let loops = loops.clone();
         let chunk = chunk.collect_vec();
@@ -251,7 +282,9 @@ pub fn part_two(input: &str) -> Option<u32> {
                     obstacles
                 };
 
-                while let Some((position, is_loop)) = current_position.advance_pt2(&mut obstacles) {
+                while let Some((position, is_loop)) =
+                    current_position.advance_pt2(&mut obstacles, bounds)
+                {
                     current_position = position;
 
                     if is_loop {
@@ -276,13 +309,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 }
@@ -2,11 +2,11 @@ use itertools::Itertools;
 use rayon::prelude::*;
 use std::str::FromStr;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2024, 7);
 
 type InputIterator<'a> = rayon::iter::Map<rayon::str::Lines<'a>, fn(&str) -> (u64, Vec<u64>)>;
 
-fn setup(input: &str) -> InputIterator {
+fn setup(input: &str) -> InputIterator<'_> {
     input.par_lines().map(|line| {
         let mut split_line = line.split(":");
 
@@ -115,13 +115,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(11387));
     }
 }
@@ -0,0 +1,17 @@
+//! Hosts all solutions in `src/bin`, so that commands that run multiple days can run them in-process.
+//!
+//! The main binary builds this binary and hands those commands to it, see `template::cli`. Since a
+//! solution that does not compile only breaks this binary, the main binary keeps working.
+
+/// All solutions in `src/bin`, compiled into this binary so that they can be run in-process.
+#[cfg(not(test))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+fn main() {
+    #[cfg(not(test))]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
+    advent_of_code::template::cli::main();
+}
@@ -1,3 +1,7 @@
 pub mod custom_iter_tools;
 pub mod helpers;
 pub mod template;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
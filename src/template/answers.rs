@@ -0,0 +1,210 @@
+/// Module that keeps a record of accepted answers, so that refactored solutions can be verified.
+use std::{collections::HashMap, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{PuzzleId, data_dir, registry};
+
+/// The accepted answers of a day, stored in `data/<year>/answers/<day>.json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// The path of the answers file of a day, e.g. `data/2024/answers/01.json`.
+    #[must_use]
+    pub fn path(id: PuzzleId) -> PathBuf {
+        data_dir(id.year)
+            .join("answers")
+            .join(format!("{}.json", id.day))
+    }
+
+    /// Rehydrate the answers of a day. If not present, returns no answers.
+    pub fn read(id: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(Self::path(id)) {
+            Ok(contents) => Answers::try_from(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The accepted answers of a day: the recorded ones, or else the ones declared by its registered
+    /// solution, see [`Solution::EXPECTED`](crate::template::Solution::EXPECTED).
+    pub fn accepted(id: PuzzleId) -> Result<Self, String> {
+        let mut answers = Self::read(id)?;
+
+        if let Some(entry) = registry::get(id) {
+            for (part, expected) in (1..).zip(entry.expected) {
+                if let Some(expected) = expected
+                    && answers.get(part).is_none()
+                {
+                    answers.set(part, expected.to_string());
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// Dehydrate the answers of a day to its JSON file.
+    pub fn store(&self, id: PuzzleId) -> Result<(), io::Error> {
+        let path = Self::path(id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Record an accepted answer for a part and store the answers of the day.
+    pub fn record(id: PuzzleId, part: u8, answer: &str) -> Result<(), String> {
+        let mut answers = Self::read(id)?;
+        answers.set(part, answer.to_string());
+        answers.store(id).map_err(|e| e.to_string())
+    }
+
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part_1 = Some(answer),
+            2 => self.part_2 = Some(answer),
+            _ => {}
+        }
+    }
+}
+
+/// The outcome of checking a part's answer against the accepted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the accepted answer.
+    Correct,
+    /// The answer differs from the accepted answer. `actual` is `None` if the part did not return an answer.
+    Mismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No answer has been accepted for this part yet.
+    Unknown,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn check(answers: &Answers, part: u8, actual: Option<&str>) -> Self {
+        match answers.get(part) {
+            None => Verdict::Unknown,
+            Some(expected) if actual == Some(expected.as_str()) => Verdict::Correct,
+            Some(expected) => Verdict::Mismatch {
+                expected: expected.clone(),
+                actual: actual.map(Into::into),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("expected answers.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("expected answers.part_2 to be null or string.")?;
+
+        Ok(Answers {
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Verdict};
+
+    #[test]
+    fn serializes_answers() {
+        let answers = Answers {
+            part_1: Some("184122457".into()),
+            part_2: None,
+        };
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "part_1": 1 }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        };
+
+        assert_eq!(Verdict::check(&answers, 1, Some("42")), Verdict::Correct);
+        assert_eq!(
+            Verdict::check(&answers, 1, Some("41")),
+            Verdict::Mismatch {
+                expected: "42".into(),
+                actual: Some("41".into())
+            }
+        );
+        assert_eq!(
+            Verdict::check(&answers, 1, None),
+            Verdict::Mismatch {
+                expected: "42".into(),
+                actual: None
+            }
+        );
+        assert_eq!(Verdict::check(&answers, 2, Some("1")), Verdict::Unknown);
+    }
+}
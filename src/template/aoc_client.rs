@@ -0,0 +1,685 @@
+/// Client for the Advent of Code website, used to download inputs and puzzles and to submit answers.
+///
+/// Requests are authenticated with the `session` cookie of a logged-in browser. It is read from the
+/// `AOC_SESSION` environment variable, or from a `.adventofcode.session` file in the home directory.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{
+    PuzzleId, data_dir,
+    fetch_log::{self, FetchLog, Resource},
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the template to the Advent of Code maintainers, as requested for automated tools.
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or in a session file.
+    MissingSession,
+    /// The server did not accept the session cookie. It might have expired.
+    BadSession,
+    /// The puzzle does not exist or has not been unlocked yet.
+    NotFound,
+    /// The server asked to slow down. Contains the time left to wait, if reported.
+    RateLimited(Option<String>),
+    /// The server was contacted less than the minimum interval ago. Contains the time left to wait.
+    Throttled(Duration),
+    /// `AOC_MIN_FETCH_INTERVAL` is not a valid interval. Contains its value.
+    InvalidMinInterval(String),
+    /// The submitted answer is not correct. Contains whether it was too high or too low, if reported.
+    WrongAnswer(Option<Hint>),
+    /// The part cannot be submitted, because it was solved already or its previous part is not solved yet.
+    WrongLevel,
+    /// The server responded with an unexpected status code or content.
+    UnexpectedResponse(u16),
+    Request(ureq::Error),
+    IO(io::Error),
+}
+
+/// Hint given by the server for a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Hint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Hint::TooHigh => f.write_str("too high"),
+            Hint::TooLow => f.write_str("too low"),
+        }
+    }
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file `~/.adventofcode.session`."
+            ),
+            AocClientError::BadSession => write!(
+                f,
+                "the session cookie was not accepted. It might have expired, please refresh it."
+            ),
+            AocClientError::NotFound => write!(f, "the puzzle is not available (yet)."),
+            AocClientError::RateLimited(Some(wait)) => {
+                write!(
+                    f,
+                    "too many requests, please wait {wait} before trying again."
+                )
+            }
+            AocClientError::RateLimited(None) => {
+                write!(f, "too many requests, please wait before trying again.")
+            }
+            AocClientError::Throttled(wait) => write!(
+                f,
+                "the server was contacted recently. Try again in {}s, or pass `--force` to skip this check.",
+                wait.as_secs().max(1)
+            ),
+            AocClientError::InvalidMinInterval(value) => write!(
+                f,
+                "`AOC_MIN_FETCH_INTERVAL` is set to `{value}`, expecting an interval such as `15m`, `90s` or `90`."
+            ),
+            AocClientError::WrongAnswer(Some(hint)) => {
+                write!(f, "that's not the right answer, your answer is {hint}.")
+            }
+            AocClientError::WrongAnswer(None) => write!(f, "that's not the right answer."),
+            AocClientError::WrongLevel => write!(
+                f,
+                "this part cannot be submitted. Did you already complete it, or not yet complete the previous part?"
+            ),
+            AocClientError::UnexpectedResponse(status) => {
+                write!(f, "unexpected response from server (status {status}).")
+            }
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Request(e)
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    /// Create a client for the Advent of Code website.
+    #[must_use]
+    pub fn new(session: String) -> Self {
+        Self::with_base_url(session, BASE_URL)
+    }
+
+    /// Create a client for a server at `base_url`, e.g. a local stub server in tests.
+    #[must_use]
+    pub fn with_base_url(session: String, base_url: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(TIMEOUT))
+            .build()
+            .into();
+
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent,
+        }
+    }
+
+    /// Create a client with the session cookie from the environment or a session file.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        Ok(Self::new(read_session()?))
+    }
+
+    /// Fetch the puzzle input of a day.
+    pub fn input(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/day/{}/input", self.year_url(id), id.day.into_inner());
+
+        read_body(
+            self.agent
+                .get(&url)
+                .header("Cookie", self.cookie())
+                .call()?,
+        )
+    }
+
+    /// Fetch the description of a day's puzzle as markdown.
+    /// The second part is only included once the first part is solved.
+    pub fn puzzle(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/day/{}", self.year_url(id), id.day.into_inner());
+
+        let html = read_body(
+            self.agent
+                .get(&url)
+                .header("Cookie", self.cookie())
+                .call()?,
+        )?;
+        Ok(html_to_markdown(&html))
+    }
+
+    /// Submit an answer for a part. Returns `Ok` if the answer is correct.
+    pub fn submit(&self, id: PuzzleId, part: u8, answer: &str) -> Result<(), AocClientError> {
+        let url = format!("{}/day/{}/answer", self.year_url(id), id.day.into_inner());
+
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+
+        parse_submission(&read_body(response)?)
+    }
+
+    fn year_url(&self, id: PuzzleId) -> String {
+        format!("{}/{}", self.base_url, id.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Download the input and the puzzle description of a day to the data directory.
+///
+/// Files that are already present are not downloaded again, and the server is not contacted if it was
+/// contacted less than the [minimum interval](fetch_log::min_interval) ago. `force` skips both checks.
+pub fn download(id: PuzzleId, force: bool) -> Result<(), AocClientError> {
+    let input_path = get_input_path(id);
+    let puzzle_path = get_puzzle_path(id);
+
+    let resources: Vec<(Resource, &PathBuf)> = [
+        (Resource::Input, &input_path),
+        (Resource::Puzzle, &puzzle_path),
+    ]
+    .into_iter()
+    .filter(|(_, path)| force || !is_present(path))
+    .collect();
+
+    if resources.is_empty() {
+        println!(
+            "🎄 Input and puzzle are already present in {:?}. Pass `--force` to download them again.",
+            data_dir(id.year)
+        );
+        return Ok(());
+    }
+
+    let mut log = FetchLog::read_from_file();
+    check_interval(&log, force)?;
+
+    let client = AocClient::from_env()?;
+
+    for (resource, path) in resources {
+        let content = match resource {
+            Resource::Input => client.input(id)?,
+            Resource::Puzzle => client.puzzle(id)?,
+        };
+
+        // store every fetch right away, so that it counts towards the interval if a later step fails.
+        let is_changed = log.record(id, resource, &content, fetch_log::now());
+        log.store_file()?;
+        write_file(path, &content)?;
+        println!(
+            "🎄 Successfully wrote {resource} to {path:?}{}.",
+            if is_changed {
+                ""
+            } else {
+                " (unchanged since the last download)"
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// Download the puzzle description of a day to the data directory and print it.
+///
+/// If the puzzle was downloaded less than the [minimum interval](fetch_log::min_interval) ago,
+/// it is printed from disk instead. `force` always downloads it.
+pub fn read(id: PuzzleId, force: bool) -> Result<(), AocClientError> {
+    let puzzle_path = get_puzzle_path(id);
+    let mut log = FetchLog::read_from_file();
+    let min_interval = fetch_log::min_interval().map_err(AocClientError::InvalidMinInterval)?;
+
+    let is_fresh = log.last(id, Resource::Puzzle).is_some_and(|fetch| {
+        fetch_log::now().saturating_sub(fetch.fetched_at) < min_interval.as_secs()
+    });
+
+    if !force && is_fresh && is_present(&puzzle_path) {
+        println!("{}", fs::read_to_string(&puzzle_path)?);
+        return Ok(());
+    }
+
+    check_interval(&log, force)?;
+
+    let puzzle = AocClient::from_env()?.puzzle(id)?;
+    log.record(id, Resource::Puzzle, &puzzle, fetch_log::now());
+    log.store_file()?;
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// The title of a downloaded puzzle, e.g. `Historian Hysteria`. `None` if the puzzle was not downloaded.
+#[must_use]
+pub fn puzzle_title(id: PuzzleId) -> Option<String> {
+    parse_title(&fs::read_to_string(get_puzzle_path(id)).ok()?)
+}
+
+/// Read the title from the heading of a puzzle description, e.g. `## --- Day 1: Historian Hysteria ---`.
+fn parse_title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find_map(|line| {
+        line.trim_start_matches('#')
+            .trim()
+            .strip_prefix("--- Day ")?
+            .strip_suffix(" ---")
+    })?;
+
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().to_string())
+}
+
+/// Submit an answer for a part of a day. Returns `Ok` if the answer is correct.
+pub fn submit(id: PuzzleId, part: u8, answer: &str) -> Result<(), AocClientError> {
+    AocClient::from_env()?.submit(id, part, answer)
+}
+
+/// Read the session cookie from `AOC_SESSION`, or from the first session file that exists.
+pub fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var("AOC_SESSION")
+        && !session.trim().is_empty()
+    {
+        return Ok(session.trim().to_string());
+    }
+
+    session_file_paths()
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+        .ok_or(AocClientError::MissingSession)
+}
+
+/// Session files in the locations used by `aoc-cli`.
+fn session_file_paths() -> Vec<PathBuf> {
+    let mut paths = vec![];
+
+    if let Some(home) = env::var_os("HOME") {
+        paths.push(Path::new(&home).join(".adventofcode.session"));
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+
+    if let Some(config_dir) = config_dir {
+        paths.push(config_dir.join("adventofcode.session"));
+    }
+
+    paths
+}
+
+/// Refuse to contact the server if it was contacted less than the minimum interval ago.
+fn check_interval(log: &FetchLog, force: bool) -> Result<(), AocClientError> {
+    if force {
+        return Ok(());
+    }
+
+    let min_interval = fetch_log::min_interval().map_err(AocClientError::InvalidMinInterval)?;
+
+    match log.wait_time(min_interval, fetch_log::now()) {
+        Some(wait) => Err(AocClientError::Throttled(wait)),
+        None => Ok(()),
+    }
+}
+
+/// Whether a file exists and is not empty.
+fn is_present(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|x| x.len() > 0)
+}
+
+fn get_input_path(id: PuzzleId) -> PathBuf {
+    data_dir(id.year)
+        .join("inputs")
+        .join(format!("{}.txt", id.day))
+}
+
+fn get_puzzle_path(id: PuzzleId) -> PathBuf {
+    data_dir(id.year)
+        .join("puzzles")
+        .join(format!("{}.md", id.day))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Read the body of a successful response, or map its status code to an error.
+fn read_body(mut response: ureq::http::Response<ureq::Body>) -> Result<String, AocClientError> {
+    match response.status().as_u16() {
+        200 => Ok(response.body_mut().read_to_string()?),
+        // the server answers with 400 or 500 if the session is missing or invalid.
+        400 | 401 | 403 | 500 => Err(AocClientError::BadSession),
+        404 => Err(AocClientError::NotFound),
+        429 => Err(AocClientError::RateLimited(None)),
+        status => Err(AocClientError::UnexpectedResponse(status)),
+    }
+}
+
+/// Interpret the page returned for a submission.
+fn parse_submission(html: &str) -> Result<(), AocClientError> {
+    let text = html_to_markdown(html);
+
+    if text.contains("That's the right answer") {
+        Ok(())
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Err(AocClientError::WrongAnswer(hint))
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+        Err(AocClientError::RateLimited(wait))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Err(AocClientError::WrongLevel)
+    } else if text.contains("please identify yourself") {
+        Err(AocClientError::BadSession)
+    } else {
+        Err(AocClientError::UnexpectedResponse(200))
+    }
+}
+
+/// Convert the `<article>` elements of a page to markdown.
+/// Only handles the small set of elements used in puzzle descriptions.
+fn html_to_markdown(html: &str) -> String {
+    let mut articles: Vec<&str> = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[start..start + end]);
+        rest = &rest[start + end..];
+    }
+
+    // pages without articles, e.g. error pages, are converted as a whole.
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let mut markdown = String::new();
+    let mut in_pre = false;
+
+    for article in articles {
+        let mut chars = article.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '<' {
+                markdown.push(c);
+                continue;
+            }
+
+            let tag: String = chars.by_ref().take_while(|c| *c != '>').collect();
+            let is_closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default();
+
+            match (name, is_closing) {
+                ("h2", false) => markdown.push_str("## "),
+                ("h2" | "p" | "ul", true) => markdown.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    markdown.push_str("```\n\n");
+                }
+                ("code", _) if !in_pre => markdown.push('`'),
+                ("em", _) => markdown.push('*'),
+                ("li", false) => markdown.push_str("- "),
+                ("li", true) | ("br", _) => markdown.push('\n'),
+                _ => {}
+            }
+        }
+    }
+
+    let markdown = decode_entities(&markdown);
+
+    // collapse runs of blank lines left by nested block elements.
+    let mut result = String::with_capacity(markdown.len());
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        if line.is_empty() && (result.is_empty() || result.ends_with("\n\n")) {
+            continue;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    result.trim_end().to_string() + "\n"
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{AocClient, AocClientError, Hint, html_to_markdown, parse_title};
+    use crate::puzzle;
+
+    /// Serve one canned response per request on a local port. Returns the base url and a handle
+    /// that yields the received requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let mut body_buf = vec![0; content_length];
+                reader.read_exact(&mut body_buf).unwrap();
+                request.push_str(&String::from_utf8(body_buf).unwrap());
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    fn client(base_url: &str) -> AocClient {
+        AocClient::with_base_url("abc123".into(), base_url)
+    }
+
+    #[test]
+    fn fetches_inputs_with_session() {
+        let (base_url, server) = serve(vec![(200, "1\n2\n3\n")]);
+
+        let input = client(&base_url).input(puzzle!(2024, 1)).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn reports_bad_sessions() {
+        let (base_url, server) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let res = client(&base_url).input(puzzle!(2024, 1));
+        assert!(matches!(res, Err(AocClientError::BadSession)));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reports_locked_puzzles() {
+        let (base_url, server) = serve(vec![(404, "Not Found")]);
+        let res = client(&base_url).puzzle(puzzle!(2024, 25));
+        assert!(matches!(res, Err(AocClientError::NotFound)));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve(vec![
+            (
+                200,
+                "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+            ),
+            (
+                200,
+                "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article></main>",
+            ),
+            (
+                200,
+                "<main><article><p>That's not the right answer. If you're stuck, ask for help.</p></article></main>",
+            ),
+            (
+                200,
+                "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 36s left to wait.</p></article></main>",
+            ),
+            (
+                200,
+                "<main><article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article></main>",
+            ),
+        ]);
+        let client = client(&base_url);
+        let id = puzzle!(2024, 3);
+
+        assert!(client.submit(id, 1, "42").is_ok());
+        assert!(matches!(
+            client.submit(id, 2, "43"),
+            Err(AocClientError::WrongAnswer(Some(Hint::TooHigh)))
+        ));
+        assert!(matches!(
+            client.submit(id, 2, "44"),
+            Err(AocClientError::WrongAnswer(None))
+        ));
+        assert!(matches!(
+            client.submit(id, 2, "45"),
+            Err(AocClientError::RateLimited(Some(wait))) if wait == "36s"
+        ));
+        assert!(matches!(
+            client.submit(id, 1, "42"),
+            Err(AocClientError::WrongLevel)
+        ));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/3/answer "));
+        assert!(requests[0].ends_with("level=1&answer=42"));
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>Pair up <code>1</code> and <code>3</code>.</li><li>Add &lt;them&gt; &amp; more.</li></ul>
+</article></main></body></html>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian* is always present.\n\n```\n3   4\n4   3\n```\n\n- Pair up `1` and `3`.\n- Add <them> & more.\n"
+        );
+    }
+
+    #[test]
+    fn reads_puzzle_titles() {
+        assert_eq!(
+            parse_title("## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian*...\n"),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(
+            parse_title("## --- Day 24: Crossed Wires ---"),
+            Some("Crossed Wires".into())
+        );
+        assert_eq!(parse_title("Please log in to get your puzzle input."), None);
+    }
+}
@@ -0,0 +1,466 @@
+/// Module that parses and runs the commands of the template, shared by the main and the registry binary.
+use std::env;
+use std::process::{self, Command};
+
+use crate::template::commands::{
+    all, download, examples, history, read, scaffold, solve, time, verify,
+};
+use crate::template::{registry, run_multi::child_commands};
+use args::{AppArguments, parse};
+
+#[cfg(feature = "today")]
+use crate::template::PuzzleId;
+
+mod args {
+    use crate::template::{
+        Day, DaySelection, PuzzleId, Year,
+        commands::{
+            scaffold::ScaffoldOptions,
+            time::{CompareOptions, DEFAULT_THRESHOLD, TimingsOptions},
+        },
+        runner::{BenchOptions, OutputFormat, parse_duration},
+    };
+    use std::{convert::Infallible, path::PathBuf, process, time::Duration};
+
+    pub enum AppArguments {
+        Download {
+            id: PuzzleId,
+            force: bool,
+        },
+        Read {
+            id: PuzzleId,
+            force: bool,
+        },
+        Scaffold {
+            id: PuzzleId,
+            download: bool,
+            overwrite: bool,
+            options: ScaffoldOptions,
+        },
+        Solve {
+            id: PuzzleId,
+            release: bool,
+            dhat: bool,
+            submit: Option<u8>,
+            input: Option<PathBuf>,
+            format: OutputFormat,
+            timeout: Option<Duration>,
+            watch: bool,
+        },
+        All {
+            year: Year,
+            selection: DaySelection,
+            release: bool,
+            isolated: bool,
+            format: OutputFormat,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+        Time {
+            year: Year,
+            all: bool,
+            selection: DaySelection,
+            isolated: bool,
+            bench: BenchOptions,
+            format: OutputFormat,
+            timings: TimingsOptions,
+        },
+        History {
+            id: PuzzleId,
+            format: OutputFormat,
+        },
+        Verify {
+            year: Year,
+            selection: DaySelection,
+            release: bool,
+            isolated: bool,
+        },
+        Examples {
+            year: Year,
+            selection: DaySelection,
+        },
+        #[cfg(feature = "today")]
+        Today,
+    }
+
+    impl AppArguments {
+        /// Whether the command runs solutions in-process, which needs the registry of solutions.
+        /// Isolated runs, runs with a timeout and parallel runs start every day as its own binary.
+        pub fn needs_registry(&self) -> bool {
+            match self {
+                AppArguments::All {
+                    isolated,
+                    timeout,
+                    jobs,
+                    ..
+                } => !isolated && timeout.is_none() && *jobs <= 1,
+                AppArguments::Time { isolated, .. } | AppArguments::Verify { isolated, .. } => {
+                    !isolated
+                }
+                AppArguments::Examples { .. } => true,
+                _ => false,
+            }
+        }
+    }
+
+    /// Reads the `--year` option, falling back to the `AOC_YEAR` environment variable.
+    ///
+    /// NOTE: must be called before any free-standing argument is parsed.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env()
+                .ok_or_else(|| "no year specified. Pass `--year <year>` or set `AOC_YEAR`.".into()),
+        }
+    }
+
+    /// Reads the `--format` option, defaulting to human-readable output.
+    fn parse_format(
+        args: &mut pico_args::Arguments,
+    ) -> Result<OutputFormat, Box<dyn std::error::Error>> {
+        Ok(args.opt_value_from_str("--format")?.unwrap_or_default())
+    }
+
+    /// Checks that a day is part of the calendar of the given year.
+    fn to_puzzle_id(year: Year, day: Day) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        PuzzleId::new(year, day).ok_or_else(|| {
+            format!(
+                "day {day} is not part of the {year} calendar, which has {} days.",
+                year.days()
+            )
+            .into()
+        })
+    }
+
+    /// Checks that a baseline name can be used as a file name.
+    fn parse_baseline_name(name: &str) -> Result<String, String> {
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Ok(name.to_string())
+        } else {
+            Err(format!(
+                "invalid baseline name `{name}`: use letters, digits, `-` and `_` only."
+            ))
+        }
+    }
+
+    /// Reads the day selection of a command that runs multiple days, and checks it against the calendar.
+    ///
+    /// NOTE: must be called after all other options are parsed.
+    fn parse_selection(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<DaySelection, Box<dyn std::error::Error>> {
+        let selection = DaySelection::from_args(args)?;
+        selection.validate(year)?;
+        Ok(selection)
+    }
+
+    /// Reads the `--year` option and the day into a [`PuzzleId`].
+    fn parse_puzzle_id(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        to_puzzle_id(year, args.free_from_str()?)
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+
+        let app_args = match args.subcommand()?.as_deref() {
+            Some("all") => {
+                let year = parse_year(&mut args)?;
+
+                AppArguments::All {
+                    year,
+                    release: args.contains("--release"),
+                    isolated: args.contains("--isolated"),
+                    format: parse_format(&mut args)?,
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    selection: parse_selection(&mut args, year)?,
+                }
+            }
+            Some("time") if args.contains("--history") => AppArguments::History {
+                format: parse_format(&mut args)?,
+                id: parse_puzzle_id(&mut args)?,
+            },
+            Some("time") => {
+                let year = parse_year(&mut args)?;
+                let all = args.contains("--all");
+                let store = args.contains("--store");
+                let isolated = args.contains("--isolated");
+                let format = parse_format(&mut args)?;
+                let bench = args
+                    .opt_value_from_fn("--budget", parse_duration)?
+                    .map_or_else(BenchOptions::default, |budget| BenchOptions { budget });
+                let is_compare = args.contains("--compare");
+                let baseline = args.opt_value_from_fn("--baseline", parse_baseline_name)?;
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(DEFAULT_THRESHOLD);
+                let save_baseline =
+                    args.opt_value_from_fn("--save-baseline", parse_baseline_name)?;
+                let note = args.opt_value_from_str("--note")?;
+                let selection = parse_selection(&mut args, year)?;
+
+                // stored timings of a day always hold both parts.
+                if selection.part.is_some() && (store || save_baseline.is_some()) {
+                    return Err(
+                        "`--store` and `--save-baseline` can't be combined with `--part`.".into(),
+                    );
+                }
+
+                // `--baseline` selects what to compare against and implies `--compare`.
+                let compare = (is_compare || baseline.is_some()).then_some(CompareOptions {
+                    baseline,
+                    threshold,
+                });
+
+                AppArguments::Time {
+                    year,
+                    all,
+                    selection,
+                    isolated,
+                    bench,
+                    format,
+                    timings: TimingsOptions {
+                        store,
+                        note,
+                        compare,
+                        save_baseline,
+                    },
+                }
+            }
+            Some("verify") => {
+                let year = parse_year(&mut args)?;
+                let release = args.contains("--release");
+                let isolated = args.contains("--isolated");
+                let selection = parse_selection(&mut args, year)?;
+
+                AppArguments::Verify {
+                    year,
+                    selection,
+                    release,
+                    isolated,
+                }
+            }
+            Some("examples") => {
+                let year = parse_year(&mut args)?;
+                let selection = parse_selection(&mut args, year)?;
+
+                AppArguments::Examples { year, selection }
+            }
+            Some("download") => {
+                let force = args.contains("--force");
+
+                AppArguments::Download {
+                    id: parse_puzzle_id(&mut args)?,
+                    force,
+                }
+            }
+            Some("read") => {
+                let force = args.contains("--force");
+
+                AppArguments::Read {
+                    id: parse_puzzle_id(&mut args)?,
+                    force,
+                }
+            }
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let options = ScaffoldOptions {
+                    template: args.opt_value_from_str("--template")?,
+                    title: args.opt_value_from_str("--title")?,
+                    answer_type: args.opt_value_from_str("--answer-type")?,
+                };
+
+                AppArguments::Scaffold {
+                    id: parse_puzzle_id(&mut args)?,
+                    download,
+                    overwrite,
+                    options,
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let input = args
+                    .opt_value_from_os_str("--input", |x| Ok::<_, Infallible>(PathBuf::from(x)))?;
+                let format = parse_format(&mut args)?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_duration)?;
+                let watch = args.contains("--watch");
+
+                // watch mode runs the built binary against the real input until interrupted, and
+                // prints for humans only.
+                if watch
+                    && (submit.is_some() || dhat || input.is_some() || format == OutputFormat::Json)
+                {
+                    return Err(
+                        "`--watch` can't be combined with `--submit`, `--dhat`, `--input` or `--format json`."
+                            .into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    id: parse_puzzle_id(&mut args)?,
+                    release,
+                    dhat,
+                    submit,
+                    input,
+                    format,
+                    timeout,
+                    watch,
+                }
+            }
+            #[cfg(feature = "today")]
+            Some("today") => AppArguments::Today,
+            Some(x) => {
+                eprintln!("Unknown command: {x}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("No command specified.");
+                process::exit(1);
+            }
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(app_args)
+    }
+}
+
+/// Parse the command line and run the command.
+///
+/// Commands that run solutions in-process are handed to the registry binary, which has all solutions
+/// compiled in, unless this already is the registry binary. This binary does not depend on any solution,
+/// so that one that does not compile does not break commands like `scaffold` or `download`.
+pub fn main() {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(1);
+        }
+        Ok(args) => {
+            if args.needs_registry() && !registry::is_registered() {
+                let errors = delegate_to_registry();
+
+                // the examples can only run in-process.
+                if matches!(args, AppArguments::Examples { .. }) {
+                    eprint!("{errors}");
+                } else {
+                    eprintln!("Not all solutions compile, running every day as its own binary.");
+                }
+            }
+
+            run(args);
+        }
+    }
+}
+
+/// Run the registry binary with the arguments of this process, and exit with its status.
+/// Returns the compiler errors if the registry can't be built, e.g. because a solution does not compile.
+fn delegate_to_registry() -> String {
+    // build the registry with the profile of this binary, i.e. `--release` for the cargo aliases.
+    let is_release = !cfg!(debug_assertions);
+
+    let executable = match child_commands::build_registry(is_release) {
+        Ok(executable) => executable,
+        Err(errors) => return errors,
+    };
+
+    match Command::new(executable)
+        .args(env::args_os().skip(1))
+        .status()
+    {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => format!("Failed to run the registry binary: {e}\n"),
+    }
+}
+
+fn run(args: AppArguments) {
+    match args {
+        AppArguments::All {
+            year,
+            selection,
+            release,
+            isolated,
+            format,
+            timeout,
+            jobs,
+        } => all::handle(year, &selection, release, isolated, format, timeout, jobs),
+        AppArguments::Time {
+            year,
+            selection,
+            all,
+            isolated,
+            bench,
+            format,
+            timings,
+        } => time::handle(year, &selection, all, isolated, bench, format, timings),
+        AppArguments::History { id, format } => history::handle(id, format),
+        AppArguments::Verify {
+            year,
+            selection,
+            release,
+            isolated,
+        } => verify::handle(year, &selection, release, isolated),
+        AppArguments::Examples { year, selection } => examples::handle(year, &selection),
+        AppArguments::Download { id, force } => download::handle(id, force),
+        AppArguments::Read { id, force } => read::handle(id, force),
+        AppArguments::Scaffold {
+            id,
+            download,
+            overwrite,
+            options,
+        } => {
+            scaffold::handle(id, overwrite, &options);
+            if download {
+                download::handle(id, false);
+            }
+        }
+        AppArguments::Solve {
+            id,
+            release,
+            dhat,
+            submit,
+            input,
+            format,
+            timeout,
+            watch,
+        } => {
+            if watch {
+                solve::watch(id, release, timeout);
+            } else {
+                solve::handle(id, release, dhat, submit, input.as_deref(), format, timeout);
+            }
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            match PuzzleId::today() {
+                Some(id) => {
+                    scaffold::handle(id, false, &scaffold::ScaffoldOptions::default());
+                    download::handle(id, false);
+                    read::handle(id, false)
+                }
+                None => {
+                    eprintln!(
+                        "`today` command can only be run on a day of the december calendar \
+                            (the 1st to the 25th, or to the 12th since 2025). \
+                            Please use `scaffold` with a specific day."
+                    );
+                    process::exit(1)
+                }
+            };
+        }
+    }
+}
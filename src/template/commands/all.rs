@@ -1,5 +1,75 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::run_multi::{DayOutcome, DayResults, RunOptions, run_multi};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{DaySelection, Year, runner::OutputFormat};
+
+pub fn handle(
+    year: Year,
+    selection: &DaySelection,
+    is_release: bool,
+    is_isolated: bool,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
+    let day_results = run_multi(
+        year,
+        &selection.days(year),
+        RunOptions {
+            is_release,
+            bench: None,
+            is_isolated,
+            format,
+            timeout,
+            jobs,
+            part: selection.part,
+        },
+    );
+
+    let failures = day_results
+        .iter()
+        .filter(|x| x.outcome.is_failure())
+        .count();
+
+    // keep stdout machine-readable in JSON mode.
+    if format == OutputFormat::Human {
+        print_summary(&day_results);
+    } else if failures > 0 {
+        eprintln!("{failures} day(s) failed.");
+    }
+
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+/// Print the outcome of every day, and how many days ended with each outcome.
+fn print_summary(day_results: &[DayResults]) {
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    println!("| Day | Outcome |");
+    println!("| :--- | :--- |");
+
+    for day in day_results {
+        let outcome = match day.outcome {
+            DayOutcome::Solved => "✓ solved".to_string(),
+            DayOutcome::Unsolved => format!("{ANSI_ITALIC}unsolved{ANSI_RESET}"),
+            outcome => format!("{ANSI_BOLD}✗ {}{ANSI_RESET}", outcome.name()),
+        };
+        println!("| {} | {outcome} |", day.id.day);
+    }
+
+    let count = |outcome| day_results.iter().filter(|x| x.outcome == outcome).count();
+
+    println!();
+    println!(
+        "{} solved, {} unsolved, {} failed, {} timed out.",
+        count(DayOutcome::Solved),
+        count(DayOutcome::Unsolved),
+        count(DayOutcome::Failed),
+        count(DayOutcome::TimedOut),
+    );
 }
@@ -1,16 +1,9 @@
-use crate::template::{Day, aoc_cli};
+use crate::template::{PuzzleId, aoc_client};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(id: PuzzleId, force: bool) {
+    if let Err(e) = aoc_client::download(id, force) {
+        eprintln!("failed to download day {}: {e}", id.day);
         process::exit(1);
     };
 }
@@ -0,0 +1,95 @@
+use std::{fs, process};
+
+use crate::template::examples::{Expectations, examples};
+use crate::template::runner::Status;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, DaySelection, PuzzleId, Year, registry};
+
+pub fn handle(year: Year, selection: &DaySelection) {
+    if !registry::is_registered() {
+        eprintln!("No solutions are registered, examples can't be run.");
+        process::exit(1);
+    }
+
+    let ids: Vec<PuzzleId> = selection
+        .days(year)
+        .into_iter()
+        .filter_map(|day| PuzzleId::new(year, day))
+        .collect();
+
+    println!("| Day | Example | Part | Expected | Answer | Result |");
+    println!("| :--- | :--- | ---: | ---: | ---: | :--- |");
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut unchecked = 0;
+
+    for id in ids {
+        let Some(solution) = registry::get(id) else {
+            continue;
+        };
+
+        let expectations = Expectations::read(id).unwrap_or_else(|e| {
+            eprintln!("Failed to read {:?}: {e}", Expectations::path(id));
+            process::exit(1);
+        });
+
+        let examples = examples(id).unwrap_or_else(|e| {
+            eprintln!("Failed to list the examples of day {}: {e}", id.day);
+            process::exit(1);
+        });
+
+        for example in examples {
+            let input = fs::read_to_string(&example.path).unwrap_or_else(|e| {
+                eprintln!("Failed to read {:?}: {e}", example.path);
+                process::exit(1);
+            });
+
+            for (part, answer) in (solution.answers)(&input)
+                .into_iter()
+                .filter(|(part, _)| selection.includes_part(*part))
+            {
+                let expected = expectations.get(&example.name, part);
+
+                let result = match expected {
+                    // a part that fails fails the example, whether an answer is expected or not.
+                    _ if matches!(answer, Err(Status::Error(_) | Status::Panicked(_))) => {
+                        failed += 1;
+                        format!("{ANSI_BOLD}✗{ANSI_RESET}")
+                    }
+                    None => {
+                        unchecked += 1;
+                        format!("{ANSI_ITALIC}unchecked{ANSI_RESET}")
+                    }
+                    Some(expected) if answer.as_deref() == Ok(expected) => {
+                        passed += 1;
+                        "✓".into()
+                    }
+                    Some(_) => {
+                        failed += 1;
+                        format!("{ANSI_BOLD}✗{ANSI_RESET}")
+                    }
+                };
+
+                println!(
+                    "| {} | `{}` | {} | {} | {} | {result} |",
+                    id.day,
+                    example.name,
+                    part,
+                    expected.unwrap_or("-"),
+                    match &answer {
+                        Ok(answer) => answer.as_str(),
+                        Err(Status::NotImplemented) => "-",
+                        Err(status) => status.name(),
+                    },
+                );
+            }
+        }
+    }
+
+    println!();
+    println!("{passed} passed, {failed} failed, {unchecked} without expectation.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
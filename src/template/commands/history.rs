@@ -0,0 +1,26 @@
+use std::process;
+
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+use crate::template::history::{print_history, read_for};
+use crate::template::runner::OutputFormat;
+
+pub fn handle(id: PuzzleId, format: OutputFormat) {
+    let snapshots = match read_for(id) {
+        Ok(snapshots) => snapshots,
+        Err(e) => {
+            eprintln!("Failed to read timing history: {e}");
+            process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Human => print_history(id, &snapshots),
+        OutputFormat::Json => {
+            for snapshot in &snapshots {
+                println!("{}", JsonValue::from(snapshot).stringify().unwrap());
+            }
+        }
+    }
+}
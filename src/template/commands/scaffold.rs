@@ -1,15 +1,27 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::module_templates::{
+    self, DEFAULT_ANSWER_TYPE, DEFAULT_TEMPLATE, Placeholders,
+};
+use crate::template::{PuzzleId, aoc_client, data_dir};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// How the solution module of a day is created.
+#[derive(Clone, Debug, Default)]
+pub struct ScaffoldOptions {
+    /// Name of the template in `templates/`. Uses the default template if not set.
+    pub template: Option<String>,
+    /// Title of the puzzle. Read from the downloaded puzzle if not set.
+    pub title: Option<String>,
+    /// Type of the answers of both parts. Defaults to `u32`.
+    pub answer_type: Option<String>,
+}
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +31,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +39,41 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(id: PuzzleId, overwrite: bool, options: &ScaffoldOptions) {
+    let template_name = options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+
+    // check the template before creating any file.
+    let template = module_templates::read(template_name).unwrap_or_else(|e| {
+        eprintln!("Failed to read template: {e}");
+        process::exit(1);
+    });
+
+    let placeholders = Placeholders {
+        id,
+        title: options
+            .title
+            .clone()
+            .or_else(|| aoc_client::puzzle_title(id)),
+        answer_type: options
+            .answer_type
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ANSWER_TYPE.to_string()),
+    };
+
+    let input_path = data_dir(id.year)
+        .join("inputs")
+        .join(format!("{}.txt", id.day));
+    let example_path = data_dir(id.year)
+        .join("examples")
+        .join(format!("{}.txt", id.day));
+    let module_path = Path::new("src/bin").join(format!("{}.rs", id.bin_name()));
+
+    for path in [&input_path, &example_path] {
+        if let Some(Err(e)) = path.parent().map(fs::create_dir_all) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +83,9 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(placeholders.render(&template).as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file {module_path:?} from template `{template_name}`");
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -56,7 +95,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file {input_path:?}");
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +105,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file {example_path:?}");
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +114,8 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        id.day, id.year
+    );
 }
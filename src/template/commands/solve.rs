@@ -1,9 +1,23 @@
+use std::io::{Write, stdout};
+use std::path::Path;
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::run_multi::child_commands::{self, Build};
+use crate::template::runner::{OutputFormat, PartResult};
+use crate::template::watch::{Snapshot, diff_answers};
+use crate::template::{ANSI_BOLD, ANSI_CLEAR, ANSI_ITALIC, ANSI_RESET, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    id: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<&Path>,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), id.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -18,13 +32,30 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if format != OutputFormat::Human {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(format!("{}ns", timeout.as_nanos()));
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args);
+
+    // passed separately, since the path may not be valid UTF-8. stdin is inherited for `--input -`.
+    if let Some(input) = input {
+        cmd.arg("--input").arg(input);
+    }
+
+    let mut cmd = cmd
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -32,3 +63,107 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Re-run the tests and the solution of a day whenever its binary, its examples or the library change.
+/// Shows how the answers changed since the previous run. Runs until interrupted.
+pub fn watch(id: PuzzleId, is_release: bool, timeout: Option<Duration>) {
+    let mut snapshot = Snapshot::take(id);
+    let mut changed = vec![];
+    let mut previous: Option<Vec<PartResult>> = None;
+
+    loop {
+        print!("{ANSI_CLEAR}");
+        let _ = stdout().flush();
+
+        if !changed.is_empty() {
+            println!("{ANSI_ITALIC}Changed: {}{ANSI_RESET}", changed.join(", "));
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+        println!("------");
+        run_tests(id);
+
+        println!();
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", id.day);
+        println!("------");
+
+        // the watched files changed since the last build, so every run builds again.
+        match Build::run(&[id], is_release) {
+            Ok(build) => {
+                eprint!("{}", build.errors);
+                let results = child_commands::run_solution(
+                    id,
+                    &build,
+                    None,
+                    OutputFormat::Human,
+                    timeout,
+                    None,
+                    None,
+                );
+
+                if let Some(previous) = &previous {
+                    print_changes(previous, &results.parts);
+                }
+
+                // keep the answers of the last run that had any, so that a failed build does not reset them.
+                if !results.parts.is_empty() {
+                    previous = Some(results.parts);
+                }
+            }
+            // e.g. `cargo` could not be run, which may be fixed by the next change.
+            Err(e) => eprintln!("Failed to build the solution: {e:?}"),
+        }
+
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+        let next = snapshot.wait_for_change(id);
+        changed = next
+            .changed_since(&snapshot)
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        snapshot = next;
+    }
+}
+
+/// Run the unit tests of a day's binary, forwarding their output.
+fn run_tests(id: PuzzleId) {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &id.bin_name()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if let Err(e) = status {
+        eprintln!("Failed to run the tests: {e}");
+    }
+}
+
+/// Print how the answers of a run differ from the answers of the previous run.
+fn print_changes(previous: &[PartResult], current: &[PartResult]) {
+    println!();
+    println!("{ANSI_BOLD}Changes{ANSI_RESET}");
+    println!("------");
+
+    for change in diff_answers(previous, current) {
+        let label = format!("Part {}", change.part);
+
+        match &change.previous {
+            Some(_) if !change.is_changed() => {
+                println!(
+                    "{label}: {} {ANSI_ITALIC}(unchanged){ANSI_RESET}",
+                    change.current
+                );
+            }
+            Some(previous) => {
+                println!(
+                    "{ANSI_BOLD}{label}: {previous} → {}{ANSI_RESET}",
+                    change.current
+                );
+            }
+            None => println!("{label}: {} {ANSI_ITALIC}(new){ANSI_RESET}", change.current),
+        }
+    }
+}
@@ -1,40 +1,142 @@
-use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::compare::{compare, print_report};
+use crate::template::history;
+use crate::template::run_multi::{RunOptions, run_multi, to_timings};
+use crate::template::runner::{BenchOptions, OutputFormat};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{DaySelection, PuzzleId, Year, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub use crate::template::compare::DEFAULT_THRESHOLD;
+
+/// How a run is compared against previous timings.
+#[derive(Clone, Debug)]
+pub struct CompareOptions {
+    /// Name of the baseline to compare against. Compares against `data/timings.json` if not set.
+    pub baseline: Option<String>,
+    /// Slowdown in percent above which a part is considered a regression.
+    pub threshold: f64,
+}
+
+/// What happens with the timings of a run.
+#[derive(Clone, Debug, Default)]
+pub struct TimingsOptions {
+    /// Merge the run into `data/timings.json` and the readme, and append it to the timing history.
+    pub store: bool,
+    /// Description of the stored run in the timing history.
+    pub note: Option<String>,
+    /// Compare the run against previous timings and exit non-zero on regressions.
+    pub compare: Option<CompareOptions>,
+    /// Store the run as a named baseline in `data/baselines`.
+    pub save_baseline: Option<String>,
+}
+
+pub fn handle(
+    year: Year,
+    selection: &DaySelection,
+    run_all: bool,
+    is_isolated: bool,
+    bench: BenchOptions,
+    format: OutputFormat,
+    options: TimingsOptions,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
+    let reference = options
+        .compare
+        .as_ref()
+        .map(|compare| match &compare.baseline {
+            Some(name) => match Timings::read_baseline(name) {
+                Ok(timings) => (timings, format!("baseline `{name}`")),
+                Err(e) => {
+                    eprintln!("Failed to read baseline `{name}`: {e}");
+                    process::exit(1);
+                }
+            },
+            None => (stored_timings.clone(), "stored timings".to_string()),
+        });
+
+    let mut days_to_run = selection.days(year);
+
+    // comparisons re-run days that already have timings.
+    if selection.is_all() && !run_all && reference.is_none() {
+        // when the `--all` flag is not set, filter out days that are fully benched.
+        days_to_run.retain(|day| {
+            PuzzleId::new(year, *day).is_some_and(|id| !stored_timings.is_complete(id))
+        });
+    }
+
+    let timings = to_timings(&run_multi(
+        year,
+        &days_to_run,
+        RunOptions {
+            is_release: true,
+            bench: Some(bench),
+            is_isolated,
+            format,
+            part: selection.part,
+            ..RunOptions::default()
         },
-        |day| HashSet::from([day]),
-    );
+    ));
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if options.store {
+        // take snapshots before storing, so that they refer to the state the run was taken at.
+        let snapshots = history::snapshot(&timings, options.note.as_deref());
+        if let Err(e) = history::append(&snapshots) {
+            eprintln!("Failed to append to timing history: {e}");
+        }
 
-    if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                // keep stdout machine-readable in JSON mode.
+                if format == OutputFormat::Human {
+                    println!();
+                    println!("Stored updated benchmarks.");
+                }
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if let Some(name) = &options.save_baseline {
+        match timings.store_baseline(name) {
+            Ok(()) => {
+                if format == OutputFormat::Human {
+                    println!();
+                    println!("Stored baseline `{name}`.");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to store baseline `{name}`: {e}");
+            }
+        }
+    }
+
+    if let (Some(compare_options), Some((reference, reference_name))) =
+        (&options.compare, reference)
+    {
+        let threshold = compare_options.threshold;
+        let comparisons = compare(&reference, &timings);
+
+        if format == OutputFormat::Human {
+            print_report(&comparisons, &reference_name, threshold);
+        }
+
+        let regressions = comparisons
+            .iter()
+            .filter(|x| x.is_regression(threshold))
+            .count();
+
+        if regressions > 0 {
+            if format != OutputFormat::Human {
+                eprintln!("{regressions} regression(s) above {threshold}%.");
+            }
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,82 @@
+use std::process;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::run_multi::{RunOptions, run_multi};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, DaySelection, Year};
+
+pub fn handle(year: Year, selection: &DaySelection, is_release: bool, is_isolated: bool) {
+    let day_results = run_multi(
+        year,
+        &selection.days(year),
+        RunOptions {
+            is_release,
+            is_isolated,
+            part: selection.part,
+            ..RunOptions::default()
+        },
+    );
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    let mut mismatches = 0;
+
+    for day in &day_results {
+        // falls back to the answers declared by the solution.
+        let answers = match Answers::accepted(day.id) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Failed to read {:?}: {e}", Answers::path(day.id));
+                mismatches += 1;
+                continue;
+            }
+        };
+
+        if day.outcome.is_failure() {
+            mismatches += 1;
+            println!(
+                "{ANSI_BOLD}Day {}: ✗ {}{ANSI_RESET}",
+                day.id.day,
+                day.outcome.name()
+            );
+        }
+
+        for part in (1..=2).filter(|part| selection.includes_part(*part)) {
+            let label = format!("Day {} Part {part}", day.id.day);
+
+            // a part with an accepted answer that did not run at all does not match either.
+            let Some(result) = day.parts.iter().find(|x| x.part == part) else {
+                if let Some(expected) = answers.get(part) {
+                    mismatches += 1;
+                    println!(
+                        "{ANSI_BOLD}{label}: ✗ expected {expected}, got no result{ANSI_RESET}"
+                    );
+                }
+                continue;
+            };
+
+            match Verdict::check(&answers, part, result.answer.as_deref()) {
+                Verdict::Correct => println!("{label}: ✓"),
+                Verdict::Mismatch { expected, actual } => {
+                    mismatches += 1;
+                    println!(
+                        "{ANSI_BOLD}{label}: ✗ expected {expected}, got {}{ANSI_RESET}",
+                        actual.unwrap_or_else(|| format!("nothing ({})", result.status))
+                    );
+                }
+                Verdict::Unknown => {
+                    println!("{label}: {ANSI_ITALIC}no accepted answer recorded{ANSI_RESET}");
+                }
+            }
+        }
+    }
+
+    println!();
+    if mismatches == 0 {
+        println!("{ANSI_ITALIC}All recorded answers match.{ANSI_RESET}");
+    } else {
+        println!("{ANSI_BOLD}{mismatches} answer(s) or day(s) do not match.{ANSI_RESET}");
+        process::exit(1);
+    }
+}
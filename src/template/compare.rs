@@ -0,0 +1,156 @@
+/// Module that compares benchmark times of a run against previously stored timings.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, PuzzleId, timings::Timings};
+
+/// Default threshold in percent above which a slowdown is considered a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10_f64;
+
+/// The change of a part's benchmark time between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub id: PuzzleId,
+    pub part: u8,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+}
+
+impl Comparison {
+    /// Relative change of the benchmark time in percent. Positive values are slowdowns.
+    #[must_use]
+    pub fn change_percent(&self) -> f64 {
+        if self.before_nanos == 0_f64 {
+            return 0_f64;
+        }
+        (self.after_nanos - self.before_nanos) / self.before_nanos * 100_f64
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
+    }
+}
+
+/// Compare the parts benched in `current` against the same parts in `reference`.
+/// Parts without a reference time are skipped.
+#[must_use]
+pub fn compare(reference: &Timings, current: &Timings) -> Vec<Comparison> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| {
+            let before = reference.data.iter().find(|x| x.id == timing.id)?;
+            Some((before, timing))
+        })
+        .flat_map(|(before, after)| {
+            [1, 2].into_iter().filter_map(|part| {
+                Some(Comparison {
+                    id: after.id,
+                    part,
+                    before_nanos: before.part_nanos(part)?,
+                    after_nanos: after.part_nanos(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Print a report of the comparisons, highlighting regressions above `threshold` percent.
+pub fn print_report(comparisons: &[Comparison], reference_name: &str, threshold: f64) {
+    println!();
+    println!("{ANSI_BOLD}Comparison with {reference_name}{ANSI_RESET}");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    for comparison in comparisons {
+        let line = format!(
+            "Day {} Part {}: {} → {} ({:+.1}%)",
+            comparison.id.day,
+            comparison.part,
+            format_nanos(comparison.before_nanos),
+            format_nanos(comparison.after_nanos),
+            comparison.change_percent()
+        );
+
+        if comparison.is_regression(threshold) {
+            println!("{ANSI_BOLD}{line} regression{ANSI_RESET}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|x| x.is_regression(threshold))
+        .count();
+
+    println!();
+    match regressions {
+        0 => println!("{ANSI_ITALIC}No regressions above {threshold}%.{ANSI_RESET}"),
+        n => println!("{ANSI_BOLD}{n} regression(s) above {threshold}%.{ANSI_RESET}"),
+    }
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!(
+        "{:.1?}",
+        std::time::Duration::from_secs_f64(nanos / 1_000_000_000_f64)
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Comparison, compare};
+
+    use crate::{
+        puzzle,
+        template::timings::{Timing, Timings},
+    };
+
+    fn timings(part_1: &str, part_2: Option<&str>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                id: puzzle!(2024, 1),
+                part_1: part_1.parse().ok(),
+                part_2: part_2.and_then(|x| x.parse().ok()),
+                total_nanos: 0_f64,
+            }],
+        }
+    }
+
+    #[test]
+    fn compares_parts_present_in_both() {
+        let res = compare(&timings("10.0ms", Some("1.0ms")), &timings("12.0ms", None));
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].part, 1);
+        assert_eq!(res[0].change_percent().round(), 20_f64);
+    }
+
+    #[test]
+    fn skips_days_without_reference() {
+        let reference = Timings { data: vec![] };
+        assert!(compare(&reference, &timings("12.0ms", None)).is_empty());
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let comparison = Comparison {
+            id: puzzle!(2024, 1),
+            part: 1,
+            before_nanos: 100_f64,
+            after_nanos: 115_f64,
+        };
+        assert!(comparison.is_regression(10_f64));
+        assert!(!comparison.is_regression(20_f64));
+
+        let faster = Comparison {
+            after_nanos: 50_f64,
+            ..comparison
+        };
+        assert_eq!(faster.change_percent(), -50_f64);
+        assert!(!faster.is_regression(10_f64));
+    }
+}
@@ -2,11 +2,7 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
-
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::Year;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -43,20 +39,6 @@ impl Day {
     }
 }
 
-#[cfg(feature = "today")]
-impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
-    }
-}
-
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -100,20 +82,23 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of advent of a year, from the 1st to the last day of its calendar.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of a year, from the 1st to the last day of its calendar.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.days(),
+        }
     }
 }
 
@@ -121,10 +106,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and a calendar never has more than 25 days.
         let day = Day(self.current);
         self.current += 1;
 
@@ -155,10 +140,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{Day, all_days};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +173,15 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_short_calendar() {
+        let days: Vec<Day> = all_days(year!(2025)).collect();
+
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.first(), Some(&Day(1)));
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
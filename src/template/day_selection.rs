@@ -0,0 +1,283 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{
+    Day, PuzzleId, Year, all_days, answers::Answers, runner::parse_part, timings::Timings,
+};
+
+/// A set of days, written as a comma-separated list of days and inclusive ranges, e.g. `1-5,7,12`.
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1-3,7".parse().unwrap();
+/// assert_eq!(days.to_string(), "01-03,07")
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<Day>);
+
+impl DaySet {
+    #[must_use]
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    /// The days of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',') {
+            let invalid = || DaySetFromStrError(item.to_string());
+
+            match item.split_once('-') {
+                Some((first, last)) => {
+                    let first: Day = first.trim().parse().map_err(|_| invalid())?;
+                    let last: Day = last.trim().parse().map_err(|_| invalid())?;
+
+                    if first > last {
+                        return Err(invalid());
+                    }
+
+                    // NOTE: both ends are valid days, so every day in between is one as well.
+                    days.extend((first.into_inner()..=last.into_inner()).filter_map(Day::new));
+                }
+                None => {
+                    days.insert(item.trim().parse().map_err(|_| invalid())?);
+                }
+            }
+        }
+
+        Ok(DaySet(days))
+    }
+}
+
+/// Displays ranges of consecutive days as `first-last`.
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(Day, Day)> = vec![];
+
+        for day in self.iter() {
+            match ranges.last_mut() {
+                Some((_, last)) if last.into_inner() + 1 == day.into_inner() => *last = day,
+                _ => ranges.push((day, day)),
+            }
+        }
+
+        let ranges: Vec<String> = ranges
+            .into_iter()
+            .map(|(first, last)| {
+                if first == last {
+                    first.to_string()
+                } else {
+                    format!("{first}-{last}")
+                }
+            })
+            .collect();
+
+        f.write_str(&ranges.join(","))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`]. Contains the invalid item.
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting day numbers or ranges such as `1-5`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The days and parts a command that runs multiple days works on.
+///
+/// Shared by `all`, `time`, `verify` and `examples`, which accept `[<days>] [--only-unsolved]
+/// [--only-timed] [--part <1|2>]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DaySelection {
+    /// The selected days. Every day of the calendar if not set.
+    pub days: Option<DaySet>,
+    /// Only days that have no accepted answer for a selected part, set with `--only-unsolved`.
+    pub only_unsolved: bool,
+    /// Only days that have stored timings for a selected part, set with `--only-timed`.
+    pub only_timed: bool,
+    /// Only run this part, set with `--part <1|2>`.
+    pub part: Option<u8>,
+}
+
+impl DaySelection {
+    /// Reads the filter options and the optional day set.
+    ///
+    /// NOTE: must be called after all other options are parsed, since the day set is free-standing.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, Box<dyn Error>> {
+        Ok(DaySelection {
+            only_unsolved: args.contains("--only-unsolved"),
+            only_timed: args.contains("--only-timed"),
+            part: args.opt_value_from_fn("--part", parse_part)?,
+            days: args.opt_free_from_str()?,
+        })
+    }
+
+    /// Checks that all selected days are part of the calendar of the given year.
+    pub fn validate(&self, year: Year) -> Result<(), String> {
+        let Some(days) = &self.days else {
+            return Ok(());
+        };
+
+        match days.iter().find(|day| PuzzleId::new(year, *day).is_none()) {
+            Some(day) => Err(format!(
+                "day {day} is not part of the {year} calendar, which has {} days.",
+                year.days()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether every day of the calendar is selected, i.e. no days and no filters are set.
+    /// The selected part does not restrict the days.
+    #[must_use]
+    pub fn is_all(&self) -> bool {
+        self.days.is_none() && !self.only_unsolved && !self.only_timed
+    }
+
+    /// Whether a part is selected.
+    #[must_use]
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|x| x == part)
+    }
+
+    /// The selected days of a year, in order. Filters read accepted answers and stored timings from disk.
+    #[must_use]
+    pub fn days(&self, year: Year) -> BTreeSet<Day> {
+        let timings = self.only_timed.then(Timings::read_from_file);
+
+        all_days(year)
+            .filter(|day| self.days.as_ref().is_none_or(|days| days.contains(*day)))
+            .filter(|day| {
+                let Some(id) = PuzzleId::new(year, *day) else {
+                    return false;
+                };
+
+                (!self.only_unsolved || !self.is_solved(id))
+                    && timings
+                        .as_ref()
+                        .is_none_or(|timings| self.is_timed(timings, id))
+            })
+            .collect()
+    }
+
+    /// Whether all selected parts of a day have an accepted answer, recorded on submission or declared
+    /// by the solution.
+    fn is_solved(&self, id: PuzzleId) -> bool {
+        let answers = Answers::accepted(id).unwrap_or_default();
+
+        (1..=2)
+            .filter(|part| self.includes_part(*part))
+            .all(|part| answers.get(part).is_some())
+    }
+
+    /// Whether any selected part of a day has stored timings.
+    fn is_timed(&self, timings: &Timings, id: PuzzleId) -> bool {
+        timings.data.iter().any(|timing| {
+            timing.id == id
+                && (1..=2).any(|part| self.includes_part(part) && timing.part(part).is_some())
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySelection, DaySet};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_days_and_ranges() {
+        let days: DaySet = "1-5,7,12".parse().unwrap();
+
+        assert_eq!(
+            days.iter().collect::<Vec<_>>(),
+            vec![
+                day!(1),
+                day!(2),
+                day!(3),
+                day!(4),
+                day!(5),
+                day!(7),
+                day!(12)
+            ]
+        );
+        assert_eq!(days.to_string(), "01-05,07,12");
+    }
+
+    #[test]
+    fn parses_single_days() {
+        let days: DaySet = "8".parse().unwrap();
+
+        assert_eq!(days.iter().collect::<Vec<_>>(), vec![day!(8)]);
+    }
+
+    #[test]
+    fn merges_overlapping_ranges() {
+        let days: DaySet = "3-6, 5-8,4".parse().unwrap();
+
+        assert_eq!(days.to_string(), "03-08");
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert!("0".parse::<DaySet>().is_err());
+        assert!("26".parse::<DaySet>().is_err());
+        assert!("5-3".parse::<DaySet>().is_err());
+        assert!("1-".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("one".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn selects_days_of_the_calendar() {
+        let selection = DaySelection {
+            days: Some("10-14".parse().unwrap()),
+            ..DaySelection::default()
+        };
+
+        assert!(selection.validate(year!(2024)).is_ok());
+        assert!(selection.validate(year!(2025)).is_err());
+
+        assert_eq!(
+            selection.days(year!(2024)).into_iter().collect::<Vec<_>>(),
+            vec![day!(10), day!(11), day!(12), day!(13), day!(14)]
+        );
+
+        assert_eq!(DaySelection::default().days(year!(2025)).len(), 12);
+    }
+
+    #[test]
+    fn selects_parts() {
+        let selection = DaySelection {
+            part: Some(2),
+            ..DaySelection::default()
+        };
+
+        assert!(!selection.includes_part(1));
+        assert!(selection.includes_part(2));
+        assert!(DaySelection::default().includes_part(1));
+    }
+}
@@ -0,0 +1,161 @@
+/// Module that finds the example inputs of a day and the answers expected for them.
+use std::{fs, io, path::PathBuf, str::FromStr};
+
+use crate::template::{PuzzleId, data_dir};
+
+/// An example input of a day, e.g. `data/2024/examples/03-2.txt`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The file name without extension, e.g. `03-2`.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The example inputs of a day: `<day>.txt` and `<day>-<suffix>.txt`, sorted by name.
+pub fn examples(id: PuzzleId) -> Result<Vec<Example>, io::Error> {
+    let dir = data_dir(id.year).join("examples");
+    let day = id.day.to_string();
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut examples = vec![];
+
+    for entry in entries {
+        let path = entry?.path();
+
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+
+        let Some(name) = path.file_stem().and_then(|x| x.to_str()).map(String::from) else {
+            continue;
+        };
+
+        if name == day || name.strip_prefix(&day).is_some_and(|x| x.starts_with('-')) {
+            examples.push(Example { name, path });
+        }
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// The answer expected for a part when run against an example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expectation {
+    /// The name of the example, e.g. `03-2`.
+    pub example: String,
+    pub part: u8,
+    pub answer: String,
+}
+
+/// The expected answers of a day, stored in `data/<year>/examples/<day>.expected`.
+///
+/// Every line declares one answer as `<example> <part> <answer>`, e.g. `03-2 2 48`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expectations {
+    pub data: Vec<Expectation>,
+}
+
+impl Expectations {
+    /// The path of the expectations file of a day, e.g. `data/2024/examples/01.expected`.
+    #[must_use]
+    pub fn path(id: PuzzleId) -> PathBuf {
+        data_dir(id.year)
+            .join("examples")
+            .join(format!("{}.expected", id.day))
+    }
+
+    /// Read the expectations of a day. If not present, returns no expectations.
+    pub fn read(id: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(Self::path(id)) {
+            Ok(contents) => Expectations::from_str(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Expectations::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The answer expected for a part when run against an example.
+    #[must_use]
+    pub fn get(&self, example: &str, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|x| x.example == example && x.part == part)
+            .map(|x| x.answer.as_str())
+    }
+}
+
+impl FromStr for Expectations {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut data = vec![];
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || format!("line {}: expected `<example> <part> <answer>`.", index + 1);
+
+            let (example, rest) = line.split_once(char::is_whitespace).ok_or_else(error)?;
+            let (part, answer) = rest
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .ok_or_else(error)?;
+
+            let part = match part.parse() {
+                Ok(part @ (1 | 2)) => part,
+                _ => return Err(format!("line {}: expected part 1 or 2.", index + 1)),
+            };
+
+            data.push(Expectation {
+                example: example.to_string(),
+                part,
+                answer: answer.trim().to_string(),
+            });
+        }
+
+        Ok(Expectations { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Expectation, Expectations};
+
+    #[test]
+    fn parses_expectations() {
+        let expectations =
+            Expectations::from_str("# example part answer\n03 1 161\n\n03-2  2 48\n06 1 a b\n")
+                .unwrap();
+
+        assert_eq!(
+            expectations.data[1],
+            Expectation {
+                example: "03-2".into(),
+                part: 2,
+                answer: "48".into()
+            }
+        );
+        assert_eq!(expectations.get("03", 1), Some("161"));
+        assert_eq!(expectations.get("03", 2), None);
+        assert_eq!(expectations.get("06", 1), Some("a b"));
+    }
+
+    #[test]
+    fn rejects_invalid_expectations() {
+        assert!(Expectations::from_str("03 161").is_err());
+        assert!(Expectations::from_str("03 3 161").is_err());
+    }
+}
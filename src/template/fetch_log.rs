@@ -0,0 +1,347 @@
+/// Module that records requests to the Advent of Code website, so that downloads can be served
+/// from disk and the server is not contacted more often than a minimum interval.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs, io,
+    path::Path,
+    process,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year, runner::parse_duration};
+
+static FETCH_LOG_FILE_PATH: &str = "./data/fetches.json";
+
+/// Minimum interval between requests if `AOC_MIN_FETCH_INTERVAL` is not set.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A resource downloaded from the website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    Input,
+    Puzzle,
+}
+
+impl Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::Input => f.write_str("input"),
+            Resource::Puzzle => f.write_str("puzzle"),
+        }
+    }
+}
+
+impl FromStr for Resource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(Resource::Input),
+            "puzzle" => Ok(Resource::Puzzle),
+            _ => Err(format!("unknown resource `{s}`.")),
+        }
+    }
+}
+
+/// A single download of a resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fetch {
+    pub id: PuzzleId,
+    pub resource: Resource,
+    /// Seconds since the unix epoch.
+    pub fetched_at: u64,
+    /// FNV-1a hash of the downloaded content, as hex string.
+    pub hash: String,
+}
+
+/// The downloads of all days, stored in `data/fetches.json`.
+#[derive(Clone, Debug, Default)]
+pub struct FetchLog {
+    pub data: Vec<Fetch>,
+}
+
+impl FetchLog {
+    /// Rehydrate the log from its JSON file. If not present, returns an empty log.
+    /// Exits if the file can't be read or parsed, so that the throttle is not skipped and the log is not
+    /// overwritten with fewer fetches.
+    pub fn read_from_file() -> Self {
+        if !Path::new(FETCH_LOG_FILE_PATH).exists() {
+            return FetchLog::default();
+        }
+
+        match fs::read_to_string(FETCH_LOG_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(FetchLog::try_from)
+        {
+            Ok(log) => log,
+            Err(e) => {
+                eprintln!("Failed to read the fetch log from {FETCH_LOG_FILE_PATH:?}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Dehydrate the log to its JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(FETCH_LOG_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Record a download of a resource. Returns whether the content changed since the previous download.
+    pub fn record(&mut self, id: PuzzleId, resource: Resource, content: &str, now: u64) -> bool {
+        let hash = hash(content);
+        let is_changed = self
+            .last(id, resource)
+            .is_none_or(|fetch| fetch.hash != hash);
+
+        self.data.retain(|x| x.id != id || x.resource != resource);
+        self.data.push(Fetch {
+            id,
+            resource,
+            fetched_at: now,
+            hash,
+        });
+        self.data
+            .sort_by_key(|x| (x.id, x.resource == Resource::Puzzle));
+
+        is_changed
+    }
+
+    /// The last download of a resource.
+    #[must_use]
+    pub fn last(&self, id: PuzzleId, resource: Resource) -> Option<&Fetch> {
+        self.data
+            .iter()
+            .find(|x| x.id == id && x.resource == resource)
+    }
+
+    /// Time left to wait until the server may be contacted again, if any.
+    #[must_use]
+    pub fn wait_time(&self, min_interval: Duration, now: u64) -> Option<Duration> {
+        let last = self.data.iter().map(|x| x.fetched_at).max()?;
+        let elapsed = Duration::from_secs(now.saturating_sub(last));
+        (elapsed < min_interval).then(|| min_interval - elapsed)
+    }
+}
+
+/// The minimum interval between requests, configurable through `AOC_MIN_FETCH_INTERVAL`, e.g. `15m` or `90s`.
+/// Returns the invalid value as error, see [`parse_interval`].
+pub fn min_interval() -> Result<Duration, String> {
+    match env::var("AOC_MIN_FETCH_INTERVAL") {
+        Ok(value) => parse_interval(&value).ok_or(value),
+        Err(_) => Ok(DEFAULT_MIN_INTERVAL),
+    }
+}
+
+/// Parse an interval like `15m` or `90s`. Values without a unit are read as seconds, not as milliseconds
+/// like in [`parse_duration`].
+fn parse_interval(s: &str) -> Option<Duration> {
+    let s = s.trim();
+
+    if let Ok(seconds) = s.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    // support minutes in addition to the units of `parse_duration`.
+    match s.strip_suffix('m').and_then(|m| m.parse::<u64>().ok()) {
+        Some(minutes) => Some(Duration::from_secs(minutes * 60)),
+        None => parse_duration(s).ok(),
+    }
+}
+
+/// Seconds since the unix epoch.
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+/// FNV-1a, which is stable across platforms and rust versions, unlike the std hasher.
+fn hash(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&FetchLog> for JsonValue {
+    fn from(value: &FetchLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for FetchLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(FetchLog {
+            data: json_data
+                .iter()
+                .map(Fetch::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Fetch> for JsonValue {
+    fn from(value: &Fetch) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("year".into(), JsonValue::String(value.id.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.id.day.to_string()));
+        map.insert(
+            "resource".into(),
+            JsonValue::String(value.resource.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "fetched_at".into(),
+            JsonValue::Number(value.fetched_at as f64),
+        );
+        map.insert("hash".into(), JsonValue::String(value.hash.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fetch {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fetch to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected fetch.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected fetch.day to be a Day struct.")?;
+
+        let resource = json
+            .get("resource")
+            .and_then(|v| v.get::<String>())
+            .and_then(|resource| Resource::from_str(resource).ok())
+            .ok_or("Expected fetch.resource to be `input` or `puzzle`.")?;
+
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<f64>())
+            .filter(|x| **x >= 0_f64)
+            .map(|x| *x as u64)
+            .ok_or("Expected fetch.fetched_at to be a positive number.")?;
+
+        let hash = json
+            .get("hash")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected fetch.hash to be a string.")?;
+
+        Ok(Fetch {
+            id: PuzzleId::new(year, day)
+                .ok_or("Expected fetch.day to be part of the calendar of fetch.year.")?,
+            resource,
+            fetched_at,
+            hash: hash.clone(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{FetchLog, Resource, hash, parse_interval};
+    use crate::puzzle;
+
+    #[test]
+    fn records_fetches() {
+        let mut log = FetchLog::default();
+        let id = puzzle!(2024, 1);
+
+        assert!(log.record(id, Resource::Input, "1 2", 100));
+        assert!(log.record(id, Resource::Puzzle, "## Day 1", 101));
+        assert!(!log.record(id, Resource::Input, "1 2", 200));
+        assert!(log.record(id, Resource::Puzzle, "## Day 1\n## Part Two", 201));
+
+        assert_eq!(log.data.len(), 2);
+        assert_eq!(log.last(id, Resource::Input).unwrap().fetched_at, 200);
+        assert_eq!(log.last(id, Resource::Input).unwrap().hash, hash("1 2"));
+    }
+
+    #[test]
+    fn computes_wait_time() {
+        let mut log = FetchLog::default();
+        let min_interval = Duration::from_secs(30);
+        assert_eq!(log.wait_time(min_interval, 1000), None);
+
+        log.record(puzzle!(2024, 1), Resource::Input, "", 1000);
+        log.record(puzzle!(2023, 1), Resource::Input, "", 980);
+        assert_eq!(
+            log.wait_time(min_interval, 1010),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(log.wait_time(min_interval, 1030), None);
+    }
+
+    #[test]
+    fn serializes_fetch_log() {
+        let mut log = FetchLog::default();
+        log.record(puzzle!(2024, 3), Resource::Puzzle, "## Day 3", 1000);
+
+        let json = JsonValue::from(&log).stringify().unwrap();
+        let res = FetchLog::try_from(json).unwrap();
+        assert_eq!(res.data, log.data);
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn parses_intervals() {
+        assert_eq!(parse_interval("900"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_interval("15m"), Some(Duration::from_secs(900)));
+        assert_eq!(parse_interval(" 90s "), Some(Duration::from_secs(90)));
+        assert_eq!(parse_interval("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_interval("soon"), None);
+        assert_eq!(parse_interval("-5"), None);
+    }
+}
@@ -0,0 +1,332 @@
+/// Module that keeps an append-only history of benchmark times.
+/// Every stored run appends one snapshot per day, keyed by the git commit and time it was taken at.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, PuzzleId,
+    timings::{PartTiming, Timing, Timings},
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// Benchmark times of a day at a point in time.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    /// Hash of the git commit the run was taken at, if the project is a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the unix epoch, `0` if unknown, e.g. for timings imported from elsewhere.
+    pub timestamp: u64,
+    /// Free-form description of the change, e.g. the optimisation that was tried.
+    pub note: Option<String>,
+    pub timing: Timing,
+}
+
+/// Take snapshots of timings at the current commit and time.
+#[must_use]
+pub fn snapshot(timings: &Timings, note: Option<&str>) -> Vec<Snapshot> {
+    let commit = git_commit();
+    let dirty = commit.is_some() && git_is_dirty();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+
+    timings
+        .data
+        .iter()
+        .map(|timing| Snapshot {
+            commit: commit.clone(),
+            dirty,
+            timestamp,
+            note: note.map(Into::into),
+            timing: timing.clone(),
+        })
+        .collect()
+}
+
+/// Append snapshots to the history file, one JSON record per line.
+pub fn append(snapshots: &[Snapshot]) -> Result<(), io::Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    for snapshot in snapshots {
+        let line = JsonValue::from(snapshot)
+            .stringify()
+            .map_err(io::Error::other)?;
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Read the snapshots of a day, oldest first. If no history is present, returns no snapshots.
+pub fn read_for(id: PuzzleId) -> Result<Vec<Snapshot>, String> {
+    let Ok(contents) = fs::read_to_string(HISTORY_FILE_PATH) else {
+        return Ok(vec![]);
+    };
+
+    parse(&contents).map(|snapshots| {
+        snapshots
+            .into_iter()
+            .filter(|snapshot| snapshot.timing.id == id)
+            .collect()
+    })
+}
+
+fn parse(contents: &str) -> Result<Vec<Snapshot>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let json = JsonValue::from_str(line)
+                .map_err(|_| format!("line {}: not valid JSON.", i + 1))?;
+            Snapshot::try_from(&json).map_err(|e| format!("line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// Print the snapshots of a day as a table, oldest first.
+pub fn print_history(id: PuzzleId, snapshots: &[Snapshot]) {
+    println!("{ANSI_BOLD}Day {} ({}){ANSI_RESET}", id.day, id.year);
+    println!("------");
+
+    if snapshots.is_empty() {
+        println!(
+            "No history recorded. Use `cargo time {} --store` to record one.",
+            id.day
+        );
+        return;
+    }
+
+    println!("| Date | Commit | Part 1 | Part 2 | Note |");
+    println!("| :--- | :--- | ---: | ---: | :--- |");
+
+    for snapshot in snapshots {
+        let commit = snapshot.commit.as_ref().map_or_else(
+            || "-".into(),
+            |commit| {
+                let short = &commit[..commit.len().min(7)];
+                if snapshot.dirty {
+                    format!("{short}*")
+                } else {
+                    short.to_string()
+                }
+            },
+        );
+
+        println!(
+            "| {} | `{commit}` | {} | {} | {ANSI_ITALIC}{}{ANSI_RESET} |",
+            if snapshot.timestamp == 0 {
+                "-".into()
+            } else {
+                format_timestamp(snapshot.timestamp)
+            },
+            format_part(snapshot.timing.part_1.as_ref()),
+            format_part(snapshot.timing.part_2.as_ref()),
+            snapshot.note.as_deref().unwrap_or("")
+        );
+    }
+}
+
+fn format_part(timing: Option<&PartTiming>) -> String {
+    timing.map_or_else(|| "-".into(), ToString::to_string)
+}
+
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|x| x.trim().to_string())
+}
+
+fn git_is_dirty() -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty())
+}
+
+/// Format a unix timestamp as a UTC date and time, e.g. `2024-12-03 14:05`.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Snapshot> for JsonValue {
+    fn from(value: &Snapshot) -> Self {
+        let mut map = JsonValue::from(&value.timing)
+            .get::<HashMap<String, JsonValue>>()
+            .cloned()
+            .unwrap_or_default();
+
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        map.insert(
+            "note".into(),
+            value
+                .note
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Snapshot {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected snapshot to be a JSON object.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected snapshot.commit to be null or string.")?;
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected snapshot.dirty to be a boolean.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected snapshot.timestamp to be a number.")? as u64;
+
+        let note = json
+            .get("note")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected snapshot.note to be null or string.")?;
+
+        Ok(Snapshot {
+            commit: commit.cloned(),
+            dirty,
+            timestamp,
+            note: note.cloned(),
+            timing: Timing::try_from(value)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Snapshot, format_timestamp, parse};
+    use crate::{puzzle, template::timings::Timing};
+
+    fn get_mock_snapshot() -> Snapshot {
+        Snapshot {
+            commit: "0123456789abcdef".parse().ok(),
+            dirty: true,
+            timestamp: 1_733_234_700,
+            note: "Remove string concatenation".parse().ok(),
+            timing: Timing {
+                id: puzzle!(2024, 3),
+                part_1: "52.7µs".parse().ok(),
+                part_2: "41.5µs".parse().ok(),
+                total_nanos: 94_200_f64,
+            },
+        }
+    }
+
+    #[test]
+    fn serializes_snapshots() {
+        let line = JsonValue::from(&get_mock_snapshot()).stringify().unwrap();
+        let res = parse(&format!("{line}\n\n{line}\n")).unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].commit.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(res[0].dirty, true);
+        assert_eq!(res[0].timestamp, 1_733_234_700);
+        assert_eq!(res[0].note.as_deref(), Some("Remove string concatenation"));
+        assert_eq!(res[0].timing.id, puzzle!(2024, 3));
+        assert_eq!(res[0].timing.part_1.as_ref().unwrap().to_string(), "52.7µs");
+    }
+
+    #[test]
+    fn handles_missing_commits() {
+        let snapshot = Snapshot {
+            commit: None,
+            note: None,
+            ..get_mock_snapshot()
+        };
+        let line = JsonValue::from(&snapshot).stringify().unwrap();
+        let res = parse(&line).unwrap();
+
+        assert!(res[0].commit.is_none());
+        assert!(res[0].note.is_none());
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        assert_eq!(parse("{}").unwrap_err().starts_with("line 1:"), true);
+        assert_eq!(parse("not json").unwrap_err().starts_with("line 1:"), true);
+        assert_eq!(parse("\n\n{}").unwrap_err().starts_with("line 3:"), true);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_234_700), "2024-12-03 14:05");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+}
@@ -1,68 +1,163 @@
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod cli;
 pub mod commands;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use day_selection::*;
+pub use puzzle::*;
+pub use solution::*;
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
+mod day_selection;
+mod examples;
+mod fetch_log;
+mod history;
+mod module_templates;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod solution;
+mod submissions;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+/// Clears the terminal and moves the cursor to the top left.
+pub const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Returns the data directory of a year, e.g. `data/2024`.
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    Path::new("data").join(year.to_string())
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, id: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(data_dir(id.year))
+        .join(folder)
+        .join(format!("{}.txt", id.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, id: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(id.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", id.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// Pass a type that implements [`Solution`](crate::template::Solution), e.g. `solution!(Day01)`, or the
+/// year and day of a solution that consists of the free functions `part_one` and `part_two`. For the latter,
+/// the macro implements [`Solution`](crate::template::Solution) for a generated type, `FreeFunctions`.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts return `Option<T>` or `Result<T, E>`, see [`PartOutput`](crate::template::runner::PartOutput).
+///
+/// A solution can declare a parse step with `parse = <fn>`, e.g. `solution!(2024, 1, parse = parse)`.
+/// The parse function receives the input as `&str`, is timed separately, and both parts receive a reference
+/// to its output instead of the input.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [], [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [], [part_two, 2]);
+    };
+    ($year:expr, $day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $year, $day, [$parse], [part_two, 2]);
+    };
+
+    (@impl $year:expr, $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
+        /// The free functions of this solution.
+        pub struct FreeFunctions;
+
+        impl $crate::template::Solution for FreeFunctions {
+            const PUZZLE: $crate::template::PuzzleId = $crate::puzzle!($year, $day);
+            const HAS_PARSE_STEP: bool = $crate::solution!(@has_parse $($parse)?);
+            const PARTS: &'static [u8] = &[$($part),*];
+
+            type Input<'a> = $crate::template::BoundInput<'a>;
+            type Answer1 = $crate::template::BoundAnswer;
+            type Answer2 = $crate::template::BoundAnswer;
+
+            fn parse(input: &str) -> Self::Input<'_> {
+                $( let input = $parse(input); )?
+                $crate::template::BoundInput::new(move |part| match part {
+                    $( $part => $crate::template::runner::PartOutput::into_answer($func(&input)), )*
+                    _ => Err($crate::template::runner::Status::NotImplemented),
+                })
+            }
+
+            fn part_one(input: &Self::Input<'_>) -> Self::Answer1 {
+                input.solve(1)
+            }
+
+            fn part_two(input: &Self::Input<'_>) -> Self::Answer2 {
+                input.solve(2)
+            }
+        }
+
+        $crate::solution!(FreeFunctions);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@has_parse) => {
+        false
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@has_parse $parse:expr) => {
+        true
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    ($solution:ty) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId =
+            <$solution as $crate::template::Solution>::PUZZLE;
+        /// The current year.
+        #[allow(dead_code)]
+        const YEAR: $crate::template::Year = PUZZLE.year;
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        #[allow(dead_code)]
+        const DAY: $crate::template::Day = PUZZLE.day;
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// This solution, for in-process runs.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Entry =
+            $crate::template::registry::Entry::of::<$solution>();
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            run_solution::<$solution>(&RunConfig::from_env());
         }
     };
 }
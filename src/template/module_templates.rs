@@ -0,0 +1,161 @@
+/// Module that finds and renders the named templates `scaffold` creates solution modules from.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::PuzzleId;
+
+/// The directory of the named templates, e.g. `templates/grid.txt`.
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// The template used if no `--template` is passed.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// The answer type of the parts if no `--answer-type` is passed.
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// The default template as shipped, used if `templates/default.txt` was removed.
+const BUILTIN_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
+
+/// The path of a named template, e.g. `templates/grid.txt`.
+#[must_use]
+pub fn path(name: &str) -> PathBuf {
+    PathBuf::from(TEMPLATES_DIR).join(format!("{name}.txt"))
+}
+
+/// The names of all templates in `templates/`, sorted.
+pub fn available() -> Result<Vec<String>, io::Error> {
+    let entries = match fs::read_dir(TEMPLATES_DIR) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut names = vec![];
+
+    for entry in entries {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|ext| ext == "txt")
+            && let Some(name) = path.file_stem().and_then(|x| x.to_str())
+        {
+            names.push(name.to_string());
+        }
+    }
+
+    names.sort_unstable();
+    Ok(names)
+}
+
+/// Read a named template. The default template falls back to the built-in one.
+pub fn read(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\', '.']) {
+        return Err(format!("invalid template name `{name}`."));
+    }
+
+    match fs::read_to_string(path(name)) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if name == DEFAULT_TEMPLATE {
+                return Ok(BUILTIN_TEMPLATE.to_string());
+            }
+
+            let available = available().unwrap_or_default();
+            Err(if available.is_empty() {
+                format!("template `{name}` not found, {TEMPLATES_DIR:?} has no templates.")
+            } else {
+                format!(
+                    "template `{name}` not found, available templates: {}.",
+                    available.join(", ")
+                )
+            })
+        }
+        Err(e) => Err(format!("failed to read template {:?}: {e}", path(name))),
+    }
+}
+
+/// The values substituted for the placeholders of a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placeholders {
+    pub id: PuzzleId,
+    /// The title of the puzzle, e.g. `Historian Hysteria`, if known.
+    pub title: Option<String>,
+    /// The type of the answers of both parts, e.g. `u64`.
+    pub answer_type: String,
+}
+
+impl Placeholders {
+    /// Substitute the placeholders of a template:
+    ///
+    /// - `%YEAR%`: the year, e.g. `2024`.
+    /// - `%DAY_NUMBER%`: the day without leading zero, e.g. `1`.
+    /// - `%DAY%`: the day with two digits, e.g. `01`.
+    /// - `%TITLE%`: the title of the puzzle, empty if not known.
+    /// - `%TITLE_OPTION%`: the title as an `Option<&str>` literal, e.g. `Some("Historian Hysteria")`.
+    /// - `%ANSWER_TYPE%`: the type of the answers, e.g. `u32`.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let title_option = self
+            .title
+            .as_ref()
+            .map_or_else(|| "None".to_string(), |title| format!("Some({title:?})"));
+
+        template
+            .replace("%YEAR%", &self.id.year.to_string())
+            .replace("%DAY_NUMBER%", &self.id.day.into_inner().to_string())
+            .replace("%DAY%", &self.id.day.to_string())
+            .replace("%TITLE_OPTION%", &title_option)
+            .replace("%TITLE%", self.title.as_deref().unwrap_or_default())
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTIN_TEMPLATE, Placeholders, read};
+    use crate::puzzle;
+
+    fn placeholders(title: Option<&str>) -> Placeholders {
+        Placeholders {
+            id: puzzle!(2024, 1),
+            title: title.map(Into::into),
+            answer_type: "u64".into(),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = placeholders(Some("Historian \"Hysteria\"")).render(
+            "solution!(%YEAR%, %DAY_NUMBER%); Day%DAY% %TITLE% %TITLE_OPTION% Option<%ANSWER_TYPE%>",
+        );
+
+        assert_eq!(
+            rendered,
+            r#"solution!(2024, 1); Day01 Historian "Hysteria" Some("Historian \"Hysteria\"") Option<u64>"#
+        );
+    }
+
+    #[test]
+    fn renders_missing_titles() {
+        assert_eq!(placeholders(None).render("%TITLE%|%TITLE_OPTION%"), "|None");
+    }
+
+    #[test]
+    fn renders_the_builtin_template() {
+        let rendered = placeholders(None).render(BUILTIN_TEMPLATE);
+
+        assert!(rendered.starts_with("advent_of_code::solution!(2024, 1);"));
+        assert!(rendered.contains("-> Option<u64>"));
+        assert!(!rendered.contains('%'));
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(read("../secrets").is_err());
+        assert!(read("").is_err());
+        assert!(read("missing-template").is_err());
+    }
+}
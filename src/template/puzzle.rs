@@ -0,0 +1,112 @@
+use std::fmt::Display;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+use crate::template::{Day, Year};
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Identifies a single puzzle by its year and day of advent.
+/// The day is guaranteed to be part of the year's calendar (see [`Year::days`]).
+///
+/// # Display
+/// This value displays as `<year>/<day>`.
+///
+/// ```
+/// # use advent_of_code::{Day, PuzzleId, Year};
+/// let id = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap()).unwrap();
+/// assert_eq!(id.to_string(), "2024/08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] if the day is part of the year's calendar,
+    /// returns [`None`] otherwise.
+    pub fn new(year: Year, day: Day) -> Option<Self> {
+        if day > year.days() {
+            return None;
+        }
+        Some(Self { year, day })
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the solution binary for this puzzle, e.g. `2024_08`.
+    pub fn bin_name(&self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the current puzzle if today is a day of this year's december calendar, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        if today.month() == 12 {
+            Self::new(
+                Year::new(u16::try_from(today.year()).ok()?)?,
+                Day::new(u8::try_from(today.day()).ok()?)?,
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {{
+        const _ASSERT: () = assert!(
+            $day <= $crate::template::Year::__new_unchecked($year).days(),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, it is not part of the calendar of year `",
+                $year,
+                "`"
+            ),
+        );
+        $crate::template::PuzzleId::__new_unchecked($crate::year!($year), $crate::day!($day))
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, year};
+
+    use super::PuzzleId;
+
+    #[test]
+    fn accepts_days_of_the_calendar() {
+        assert!(PuzzleId::new(year!(2024), day!(25)).is_some());
+        assert!(PuzzleId::new(year!(2025), day!(12)).is_some());
+    }
+
+    #[test]
+    fn rejects_days_outside_of_the_calendar() {
+        assert!(PuzzleId::new(year!(2025), day!(13)).is_none());
+        assert!(PuzzleId::new(year!(2025), day!(25)).is_none());
+    }
+}
@@ -2,7 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
+use crate::template::PuzzleId;
 use crate::template::timings::Timings;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -26,8 +26,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", id.bin_name())
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -55,22 +55,30 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    // NOTE: timings are sorted by year first, so a new section starts whenever the year changes.
+    let mut current_year = None;
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        if current_year != Some(timing.id.year) {
+            if current_year.is_some() {
+                lines.push(String::new());
+            }
+            current_year = Some(timing.id.year);
+            lines.push(format!("{prefix}# {}", timing.id.year));
+            lines.push(String::new());
+            lines.push("| Day | Part 1 | Part 2 |".into());
+            lines.push("| :---: | :---: | :---:  |".into());
+        }
+
+        let path = get_path_for_bin(timing.id);
         lines.push(format!(
             "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            timing.id.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part_1.map_or_else(|| "-".into(), |x| x.to_string()),
+            timing.part_2.map_or_else(|| "-".into(), |x| x.to_string())
         ));
     }
 
@@ -100,27 +108,27 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{puzzle, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    id: puzzle!(2024, 1),
+                    part_1: "10ms".parse().ok(),
+                    part_2: "20ms".parse().ok(),
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    id: puzzle!(2024, 2),
+                    part_1: "30ms".parse().ok(),
+                    part_2: "40ms".parse().ok(),
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    id: puzzle!(2024, 4),
+                    part_1: "40ms".parse().ok(),
+                    part_2: "50ms".parse().ok(),
                     total_nanos: 9e+10,
                 },
             ],
@@ -167,11 +175,13 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
+            "### 2024",
+            "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -0,0 +1,65 @@
+/// Registry of solutions that can be run in-process.
+///
+/// Every `solution!` invocation exports an [`Entry`] constant named `SOLUTION`.
+/// The registry binary, `src/bin/registry/main.rs`, compiles all solution binaries as modules (see `build.rs`)
+/// and registers their constants on startup, so that `all` and `time` can run any set of days without
+/// spawning `cargo`. The main binary hands these commands to it, see [`cli`](super::cli).
+use std::sync::OnceLock;
+
+use crate::template::runner::{self, BenchOptions, OutputFormat, PartResult, Status};
+use crate::template::{PuzzleId, Solution};
+
+/// Runs all implemented parts of a solution against an input and prints their results, benching them if
+/// bench options are passed. Only runs the part passed, if any. For solutions with a parse step, its result
+/// comes first, see [`PARSE_STEP`](crate::template::runner::PARSE_STEP).
+pub type RunFn = fn(&str, Option<BenchOptions>, OutputFormat, Option<u8>) -> Vec<PartResult>;
+
+/// Solves the implemented parts of a solution for an input without printing anything.
+/// Returns the answer of each part with its part number.
+pub type AnswersFn = fn(&str) -> Vec<(u8, Result<String, Status>)>;
+
+/// A single day's solution.
+#[derive(Clone, Copy, Debug)]
+pub struct Entry {
+    pub id: PuzzleId,
+    /// See [`Solution::TITLE`].
+    pub title: Option<&'static str>,
+    /// See [`Solution::EXPECTED`].
+    pub expected: [Option<&'static str>; 2],
+    pub run: RunFn,
+    pub answers: AnswersFn,
+}
+
+impl Entry {
+    /// The entry of a [`Solution`].
+    #[must_use]
+    pub const fn of<S: Solution>() -> Self {
+        Entry {
+            id: S::PUZZLE,
+            title: S::TITLE,
+            expected: S::EXPECTED,
+            run: runner::execute_solution::<S>,
+            answers: runner::solve::<S>,
+        }
+    }
+}
+
+/// The name of the binary that hosts all solutions.
+pub const BIN_NAME: &str = "registry";
+
+static SOLUTIONS: OnceLock<&'static [Entry]> = OnceLock::new();
+
+/// Registers the solutions available for in-process runs. Only the first call has an effect.
+pub fn register(solutions: &'static [Entry]) {
+    let _ = SOLUTIONS.set(solutions);
+}
+
+/// Whether solutions have been registered, i.e. in-process runs are possible.
+pub fn is_registered() -> bool {
+    SOLUTIONS.get().is_some()
+}
+
+/// Looks up the registered solution for a puzzle.
+pub fn get(id: PuzzleId) -> Option<&'static Entry> {
+    SOLUTIONS.get()?.iter().find(|solution| solution.id == id)
+}
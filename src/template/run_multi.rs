@@ -1,49 +1,337 @@
-use std::{collections::HashSet, io};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::{io, thread, time::Duration};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, PuzzleId, Year, registry,
+    runner::{BenchOptions, OutputFormat, PARSE_STEP, PartResult, Status},
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use super::timings::{PartTiming, Timing, Timings};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
+/// The results of all parts of a day that was run.
+#[derive(Clone, Debug)]
+pub struct DayResults {
+    pub id: PuzzleId,
+    pub outcome: DayOutcome,
+    pub parts: Vec<PartResult>,
+}
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+impl DayResults {
+    /// The results of a day that ran to completion, with the outcome its parts imply.
+    #[must_use]
+    pub fn new(id: PuzzleId, parts: Vec<PartResult>) -> Self {
+        DayResults {
+            id,
+            outcome: DayOutcome::from_parts(&parts),
+            parts,
+        }
+    }
 
-    let mut need_space = false;
+    /// The results of a day that did not run, e.g. because it is not scaffolded or failed to build.
+    #[must_use]
+    pub fn without_parts(id: PuzzleId, outcome: DayOutcome) -> Self {
+        DayResults {
+            id,
+            outcome,
+            parts: vec![],
+        }
+    }
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// How a day of a run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayOutcome {
+    /// At least one part returned an answer, and no part failed.
+    Solved,
+    /// The day is not scaffolded, or none of its parts is implemented.
+    Unsolved,
+    /// The day failed to build, a part returned an error or panicked, or the process crashed.
+    Failed,
+    /// A part ran longer than the timeout.
+    TimedOut,
+}
+
+impl DayOutcome {
+    /// The outcome of a day, from the results of its parts.
+    #[must_use]
+    pub fn from_parts(parts: &[PartResult]) -> Self {
+        if parts.iter().any(|x| x.status == Status::TimedOut) {
+            DayOutcome::TimedOut
+        } else if parts
+            .iter()
+            .any(|x| matches!(x.status, Status::Error(_) | Status::Panicked(_)))
+        {
+            DayOutcome::Failed
+        } else if parts
+            .iter()
+            .any(|x| x.part != PARSE_STEP && x.answer.is_some())
+        {
+            DayOutcome::Solved
+        } else {
+            DayOutcome::Unsolved
+        }
+    }
+
+    /// Whether the day failed or timed out, which fails the run.
+    #[must_use]
+    pub fn is_failure(self) -> bool {
+        matches!(self, DayOutcome::Failed | DayOutcome::TimedOut)
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            DayOutcome::Solved => "solved",
+            DayOutcome::Unsolved => "unsolved",
+            DayOutcome::Failed => "failed",
+            DayOutcome::TimedOut => "timed out",
+        }
+    }
+}
+
+/// How [`run_multi`] runs the solutions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Run optimized builds of the solution binaries.
+    pub is_release: bool,
+    /// Bench every part.
+    pub bench: Option<BenchOptions>,
+    /// Run every day as a child process of its own binary.
+    pub is_isolated: bool,
+    pub format: OutputFormat,
+    /// The time a part may run before it is reported as timed out.
+    pub timeout: Option<Duration>,
+    /// The number of days that run at the same time. Days run one after another if this is 0 or 1.
+    pub jobs: usize,
+    /// Only run this part of every day.
+    pub part: Option<u8>,
+}
+
+/// Run the solutions for a set of days and print their output.
+///
+/// Solutions run in-process if they are [`registered`](registry::is_registered), and as child processes
+/// of their own binaries if `is_isolated` is set or no registry is available.
+/// If `bench` is set, every part is benched. Returns the results and outcome of every day, solved or not.
+///
+/// Parts that panic are reported and the run continues. If a `timeout` is set, solutions run as child
+/// processes, since a part that exceeds it can only be stopped by ending its process.
+///
+/// With more than one job, days run in parallel as child processes. Their output is buffered and printed
+/// in day order. Timed runs are always sequential, so that days do not compete for the CPU.
+///
+/// Before child processes run, the binaries of all days are built at once. Days that fail to build are
+/// reported, the others run.
+pub fn run_multi(year: Year, days_to_run: &BTreeSet<Day>, options: RunOptions) -> Vec<DayResults> {
+    let RunOptions {
+        is_release,
+        bench,
+        is_isolated,
+        format,
+        timeout,
+        jobs,
+        part,
+    } = options;
+
+    let jobs = if bench.is_some() { 1 } else { jobs.max(1) };
+    let is_human = format == OutputFormat::Human;
+
+    let ids: Vec<PuzzleId> = days_to_run
+        .iter()
+        .filter_map(|day| PuzzleId::new(year, *day))
+        .collect();
+
+    let mut day_results: Vec<DayResults> = Vec::with_capacity(ids.len());
+
+    let mut finish_day = |results: DayResults| {
+        if is_human && results.outcome == DayOutcome::Unsolved && results.parts.is_empty() {
+            println!("Not solved.");
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        day_results.push(results);
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let is_child = jobs > 1 || is_isolated || timeout.is_some() || !registry::is_registered();
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    // build all binaries at once, instead of letting cargo check the build for every day.
+    let build = match is_child.then(|| child_commands::Build::run(&ids, is_release)) {
+        Some(Ok(build)) => {
+            eprint!("{}", build.errors);
+            Some(build)
+        }
+        // no day can run without its binary.
+        Some(Err(e)) => {
+            eprintln!("Failed to build the solutions: {e:?}");
+            return ids
+                .into_iter()
+                .map(|id| DayResults::without_parts(id, DayOutcome::Failed))
+                .collect();
+        }
+        None => None,
+    };
+
+    match &build {
+        Some(build) if jobs > 1 => run_parallel(
+            &ids,
+            jobs,
+            |id, buffer| {
+                child_commands::run_solution(id, build, bench, format, timeout, part, Some(buffer))
+            },
+            |index, results, buffer| {
+                if is_human {
+                    print_header(ids[index], index > 0);
+                }
+                buffer.print();
+                finish_day(results);
+            },
+        ),
+        _ => {
+            for (index, id) in ids.iter().enumerate() {
+                if is_human {
+                    print_header(*id, index > 0);
+                }
+
+                let results = match &build {
+                    Some(build) => {
+                        child_commands::run_solution(*id, build, bench, format, timeout, part, None)
+                    }
+                    None => in_process::run_solution(*id, bench, format, part),
+                };
+
+                finish_day(results);
             }
-        });
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+    if bench.is_some() && is_human {
+        let total_millis = to_timings(&day_results).total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    day_results
+}
+
+/// Print the header of a day, separated from the previous day by an empty line.
+fn print_header(id: PuzzleId, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    match registry::get(id).and_then(|x| x.title) {
+        Some(title) => println!("{ANSI_BOLD}Day {}: {title}{ANSI_RESET}", id.day),
+        None => println!("{ANSI_BOLD}Day {}{ANSI_RESET}", id.day),
+    }
+    println!("------");
+}
+
+/// Run days on up to `jobs` worker threads. `finish` receives the results and the buffered output of every
+/// day in the order of `ids`, as soon as the day and all days before it are done.
+fn run_parallel<T: Send>(
+    ids: &[PuzzleId],
+    jobs: usize,
+    run: impl Fn(PuzzleId, &mut Buffer) -> T + Sync,
+    mut finish: impl FnMut(usize, T, Buffer),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(ids.len()) {
+            let sender = sender.clone();
+            let (next, run) = (&next, &run);
+
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(id) = ids.get(index) else {
+                        break;
+                    };
+
+                    let mut buffer = Buffer::default();
+                    let results = run(*id, &mut buffer);
+
+                    if sender.send((index, results, buffer)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // the workers hold the remaining senders, so that receiving ends when all of them are done.
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_finish = 0;
+
+        for (index, results, buffer) in receiver {
+            pending.insert(index, (results, buffer));
+
+            while let Some((results, buffer)) = pending.remove(&next_to_finish) {
+                finish(next_to_finish, results, buffer);
+                next_to_finish += 1;
+            }
+        }
+    });
+}
+
+/// The output of a day that runs in parallel to others, printed once it is its turn.
+#[derive(Clone, Debug, Default)]
+pub struct Buffer {
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Buffer {
+    fn print(&self) {
+        print!("{}", self.stdout);
+        eprint!("{}", self.stderr);
+    }
+}
+
+/// Collect the benchmark times of the results of a run. Days without results are skipped.
+#[must_use]
+pub fn to_timings(day_results: &[DayResults]) -> Timings {
+    Timings {
+        data: day_results
+            .iter()
+            .filter(|day| !day.parts.is_empty())
+            .map(|day| to_timing(&day.parts, day.id))
+            .collect(),
+    }
+}
+
+/// Collect the benchmark times of a day's results. Only benched parts with an answer are considered.
+/// The time of a parse step only counts towards the total.
+fn to_timing(results: &[PartResult], id: PuzzleId) -> Timing {
+    let mut timing = Timing {
+        id,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+    };
+
+    for result in results.iter().filter(|result| {
+        (result.answer.is_some() || result.part == PARSE_STEP) && result.samples > 1
+    }) {
+        let part_timing = Some(PartTiming::from(result.stats()));
+
+        match result.part {
+            PARSE_STEP => {}
+            1 => timing.part_1 = part_timing,
+            2 => timing.part_2 = part_timing,
+            _ => continue,
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timing.total_nanos += result.duration.as_nanos() as f64;
+        }
+    }
+
+    timing
 }
 
 #[allow(dead_code)]
@@ -60,46 +348,322 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", id.bin_name())
+}
+
+/// Solutions registered in the [`registry`] can be run within the current process.
+/// This avoids the overhead of invoking `cargo` and yields typed results.
+pub mod in_process {
+    use std::fs;
+
+    use super::{DayOutcome, DayResults};
+    use crate::template::{
+        PuzzleId, data_dir, registry,
+        runner::{BenchOptions, OutputFormat},
+    };
+
+    /// Run the registered solution for a given day. Returns no results if the day is not solved.
+    pub fn run_solution(
+        id: PuzzleId,
+        bench: Option<BenchOptions>,
+        format: OutputFormat,
+        part: Option<u8>,
+    ) -> DayResults {
+        let Some(solution) = registry::get(id) else {
+            return DayResults::without_parts(id, DayOutcome::Unsolved);
+        };
+
+        let input_path = data_dir(id.year)
+            .join("inputs")
+            .join(format!("{}.txt", id.day));
+
+        let input = match fs::read_to_string(&input_path) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("could not open input file {input_path:?}: {e}");
+                return DayResults::without_parts(id, DayOutcome::Failed);
+            }
+        };
+
+        DayResults::new(id, (solution.run)(&input, bench, format, part))
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their results.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{Buffer, DayOutcome, DayResults, Error, get_path_for_bin};
+    use crate::template::{
+        PuzzleId, registry,
+        runner::{BenchOptions, OutputFormat, PartResult, format_part_result, print_part_result},
+    };
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        str::FromStr,
         thread,
+        time::Duration,
     };
+    use tinyjson::JsonValue;
+
+    /// The solution binaries of a run, built by a single `cargo build` before any of them runs.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Build {
+        /// The executable of every scaffolded day, or the compiler errors of a day that failed to build.
+        pub bins: HashMap<PuzzleId, Result<PathBuf, String>>,
+        /// Compiler errors that do not belong to a single day, e.g. errors in the library.
+        pub errors: String,
+    }
+
+    impl Build {
+        /// Build the binaries of all scaffolded days. A day that fails to build does not stop the others.
+        pub fn run(ids: &[PuzzleId], is_release: bool) -> Result<Self, Error> {
+            // skip days that have not been scaffolded yet.
+            let ids: Vec<PuzzleId> = ids
+                .iter()
+                .copied()
+                .filter(|id| Path::new(&get_path_for_bin(*id)).exists())
+                .collect();
+
+            if ids.is_empty() {
+                return Ok(Build::default());
+            }
+
+            let mut cmd = Command::new("cargo");
+            cmd.args([
+                "build",
+                "--quiet",
+                "--keep-going",
+                "--message-format",
+                "json-diagnostic-rendered-ansi",
+            ]);
+
+            if is_release {
+                cmd.arg("--release");
+            }
+
+            for id in &ids {
+                cmd.arg("--bin").arg(id.bin_name());
+            }
+
+            let output = cmd.stderr(Stdio::piped()).output()?;
+            let mut build = Build::from_messages(&ids, &String::from_utf8_lossy(&output.stdout));
+
+            // failures without compiler errors, e.g. an invalid manifest, are only explained by cargo.
+            if !output.status.success() && build.errors.is_empty() && build.is_ok() {
+                build.errors = String::from_utf8_lossy(&output.stderr).into_owned();
+            }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            Ok(build)
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        /// Read the JSON messages of `cargo build`. A day without an executable failed to build.
+        #[must_use]
+        pub fn from_messages(ids: &[PuzzleId], messages: &str) -> Self {
+            let mut executables = HashMap::new();
+            let mut day_errors: HashMap<PuzzleId, String> = HashMap::new();
+            let mut errors = String::new();
+
+            for message in messages.lines().filter_map(|x| JsonValue::from_str(x).ok()) {
+                let target = string_at(&message, &["target", "name"]);
+                let id = ids.iter().find(|id| target == Some(&id.bin_name()));
+
+                match string_at(&message, &["reason"]) {
+                    Some("compiler-artifact") => {
+                        if let (Some(id), Some(executable)) =
+                            (id, string_at(&message, &["executable"]))
+                        {
+                            executables.insert(*id, PathBuf::from(executable));
+                        }
+                    }
+                    Some("compiler-message")
+                        if string_at(&message, &["message", "level"]) == Some("error") =>
+                    {
+                        let rendered =
+                            string_at(&message, &["message", "rendered"]).unwrap_or_default();
+
+                        match id {
+                            Some(id) => day_errors.entry(*id).or_default().push_str(rendered),
+                            None => errors.push_str(rendered),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            let bins = ids
+                .iter()
+                .map(|id| match executables.remove(id) {
+                    Some(executable) => (*id, Ok(executable)),
+                    None => (*id, Err(day_errors.remove(id).unwrap_or_default())),
+                })
+                .collect();
+
+            Build { bins, errors }
+        }
+
+        /// Whether all scaffolded days were built.
+        #[must_use]
+        pub fn is_ok(&self) -> bool {
+            self.bins.values().all(Result::is_ok)
+        }
+    }
+
+    /// Build the registry binary, which hosts all solutions. Returns its executable, or the compiler errors
+    /// if it fails to build, e.g. because a solution does not compile.
+    pub fn build_registry(is_release: bool) -> Result<PathBuf, String> {
+        let mut cmd = Command::new("cargo");
+        cmd.args([
+            "build",
+            "--quiet",
+            "--bin",
+            registry::BIN_NAME,
+            "--message-format",
+            "json-diagnostic-rendered-ansi",
+        ]);
 
         if is_release {
-            args.push("--release");
+            cmd.arg("--release");
+        }
+
+        let output = cmd
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|e| format!("Failed to run `cargo build`: {e}\n"))?;
+
+        let mut executable = None;
+        let mut errors = String::new();
+
+        for message in String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|x| JsonValue::from_str(x).ok())
+        {
+            match string_at(&message, &["reason"]) {
+                Some("compiler-artifact")
+                    if string_at(&message, &["target", "name"]) == Some(registry::BIN_NAME) =>
+                {
+                    executable = string_at(&message, &["executable"]).map(PathBuf::from);
+                }
+                Some("compiler-message")
+                    if string_at(&message, &["message", "level"]) == Some("error") =>
+                {
+                    errors.push_str(
+                        string_at(&message, &["message", "rendered"]).unwrap_or_default(),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        match executable {
+            Some(executable) if output.status.success() => Ok(executable),
+            _ if errors.is_empty() => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+            _ => Err(errors),
+        }
+    }
+
+    /// The string at a path of keys in nested JSON objects.
+    fn string_at<'a>(value: &'a JsonValue, path: &[&str]) -> Option<&'a str> {
+        path.iter()
+            .try_fold(value, |value, key| {
+                value.get::<HashMap<String, JsonValue>>()?.get(*key)
+            })?
+            .get::<String>()
+            .map(String::as_str)
+    }
+
+    /// Run the built solution bin for a given day. Its output is forwarded, or written to `buffer` if passed.
+    /// Reports days that failed to build, and days whose process could not be run.
+    ///
+    /// A day whose process exits with a failure is failed, unless its parts already explain why.
+    pub fn run_solution(
+        id: PuzzleId,
+        build: &Build,
+        bench: Option<BenchOptions>,
+        format: OutputFormat,
+        timeout: Option<Duration>,
+        part: Option<u8>,
+        mut buffer: Option<&mut Buffer>,
+    ) -> DayResults {
+        match run_bin(
+            id,
+            build,
+            bench,
+            format,
+            timeout,
+            part,
+            buffer.as_deref_mut(),
+        ) {
+            Ok(results) => results,
+            Err(e) => {
+                let message = format!("Failed to run day {}: {e:?}\n", id.day);
+                match buffer {
+                    Some(buffer) => buffer.stderr.push_str(&message),
+                    None => eprint!("{message}"),
+                }
+                DayResults::without_parts(id, DayOutcome::Failed)
+            }
+        }
+    }
+
+    fn run_bin(
+        id: PuzzleId,
+        build: &Build,
+        bench: Option<BenchOptions>,
+        format: OutputFormat,
+        timeout: Option<Duration>,
+        part: Option<u8>,
+        mut buffer: Option<&mut Buffer>,
+    ) -> Result<DayResults, Error> {
+        let executable = match build.bins.get(&id) {
+            Some(Ok(executable)) => executable,
+            Some(Err(errors)) => {
+                match &mut buffer {
+                    Some(buffer) => {
+                        buffer.stderr.push_str(errors);
+                        buffer.stdout.push_str("Build failed.\n");
+                    }
+                    None => {
+                        eprint!("{errors}");
+                        println!("Build failed.");
+                    }
+                }
+                return Ok(DayResults::without_parts(id, DayOutcome::Failed));
+            }
+            // the day has not been scaffolded yet.
+            None => return Ok(DayResults::without_parts(id, DayOutcome::Unsolved)),
+        };
+
+        let budget;
+        let timeout_arg;
+        let part_arg;
+
+        // request machine-readable results from the child.
+        let mut args = vec!["--format", "json"];
+
+        if let Some(bench) = bench {
+            // mirror `--time` flag and bench options to child invocations.
+            budget = format!("{}ns", bench.budget.as_nanos());
+            args.extend(["--time", "--budget", &budget]);
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if let Some(timeout) = timeout {
+            timeout_arg = format!("{}ns", timeout.as_nanos());
+            args.extend(["--timeout", &timeout_arg]);
+        }
+
+        if let Some(part) = part {
+            part_arg = part.to_string();
+            args.extend(["--part", &part_arg]);
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing result records.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -110,148 +674,291 @@ pub mod child_commands {
 
         let mut output = vec![];
 
+        let is_buffered = buffer.is_some();
+
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            let mut buffered = String::new();
+
+            // NOTE: a read error only ends the forwarding, the exit status tells whether the day failed.
+            stderr.split(b'\n').map_while(Result::ok).for_each(|line| {
+                let line = lossy_line(&line);
+                if is_buffered {
+                    buffered.push_str(&line);
+                    buffered.push('\n');
+                } else {
+                    eprintln!("{line}");
+                }
             });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
-    }
+            buffered
+        });
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        for line in stdout.split(b'\n') {
+            let line = lossy_line(&line?);
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            match (parse_result(&line), &mut buffer) {
+                (Some(result), Some(buffer)) => {
+                    buffer.stdout.push_str(&format_part_result(&result, format));
+                    buffer.stdout.push('\n');
+                    output.push(result);
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                (Some(result), None) => {
+                    print_part_result(&result, format);
+                    output.push(result);
+                }
+                // output printed by the solution itself.
+                (None, Some(buffer)) => {
+                    buffer.stdout.push_str(&line);
+                    buffer.stdout.push('\n');
                 }
+                (None, None) => println!("{line}"),
+            }
+        }
 
-                timings.total_nanos += nanos;
-            });
+        let stderr = thread.join().unwrap();
+        if let Some(buffer) = buffer {
+            buffer.stderr = stderr;
+        }
+        let status = cmd.wait()?;
 
-        timings
-    }
+        let mut results = DayResults::new(id, output);
+        // e.g. a crash, or a failure to read the input.
+        if !status.success() && !results.outcome.is_failure() {
+            results.outcome = DayOutcome::Failed;
+        }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        Ok(results)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// A line of output without its line ending. Output that is not valid UTF-8 is replaced, not rejected.
+    fn lossy_line(line: &[u8]) -> String {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        String::from_utf8_lossy(line).into_owned()
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Parse a line of output as a [`PartResult`] record, if it is one.
+    pub fn parse_result(line: &str) -> Option<PartResult> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        PartResult::try_from(&json).ok()
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::path::PathBuf;
 
-        use crate::day;
+        use super::{Build, parse_result};
+
+        use crate::puzzle;
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+        fn parses_result_records() {
+            let res = parse_result(
+                r#"{"year":"2024","day":"01","part":2,"answer":"10","duration_nanos":74130000,"samples":99999,"min_nanos":70000000,"median_nanos":74000000,"p95_nanos":79000000,"max_nanos":80000000,"std_dev_nanos":100000,"outliers":12}"#,
+            )
+            .unwrap();
+            assert_eq!(res.id, puzzle!(2024, 1));
+            assert_eq!(res.part, 2);
+            assert_eq!(res.answer.unwrap(), "10");
+            assert_eq!(res.duration.as_nanos(), 74_130_000);
+            assert_eq!(res.samples, 99999);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn parses_missing_answers() {
+            let res = parse_result(
+                r#"{"year":"2024","day":"01","part":1,"answer":null,"duration_nanos":10,"samples":1,"min_nanos":10,"median_nanos":10,"p95_nanos":10,"max_nanos":10,"std_dev_nanos":0,"outliers":0}"#,
+            )
+            .unwrap();
+            assert_eq!(res.answer.is_none(), true);
+        }
+
+        #[test]
+        fn parses_parse_step_records() {
+            let res = parse_result(
+                r#"{"year":"2024","day":"01","part":0,"answer":null,"duration_nanos":10,"samples":1,"min_nanos":10,"median_nanos":10,"p95_nanos":10,"max_nanos":10,"std_dev_nanos":0,"outliers":0}"#,
+            )
+            .unwrap();
+            assert_eq!(res.part, 0);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn ignores_other_output() {
+            assert!(parse_result("Part 1: 0 (74.13ns @ 100000 samples)").is_none());
+            assert!(parse_result("{ debug output }").is_none());
+            assert!(parse_result(r#"{"part":1}"#).is_none());
+        }
+
+        #[test]
+        fn reads_build_messages() {
+            let ids = [puzzle!(2024, 1), puzzle!(2024, 2), puzzle!(2024, 3)];
+            let messages = [
+                r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2024_01"},"executable":"/aoc/target/release/2024_01"}"#,
+                r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"2024_02"},"message":{"level":"warning","rendered":"warning: unused variable\n"}}"#,
+                r#"{"reason":"compiler-message","target":{"kind":["bin"],"name":"2024_02"},"message":{"level":"error","rendered":"error: mismatched types\n"}}"#,
+                r#"{"reason":"compiler-message","target":{"kind":["lib"],"name":"helpers"},"message":{"level":"error","rendered":"error: unresolved import\n"}}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+                "not a message",
+            ]
+            .join("\n");
+
+            let build = Build::from_messages(&ids, &messages);
+
+            assert_eq!(
+                build.bins[&ids[0]],
+                Ok(PathBuf::from("/aoc/target/release/2024_01"))
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(build.bins[&ids[1]], Err("error: mismatched types\n".into()));
+            assert_eq!(build.bins[&ids[2]], Err(String::new()));
+            assert_eq!(build.errors, "error: unresolved import\n");
+            assert!(!build.is_ok());
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::{DayOutcome, run_parallel, to_timing};
+    use crate::{
+        puzzle,
+        template::{
+            PuzzleId, all_days,
+            runner::{PartResult, Status},
+        },
+        year,
+    };
+
+    fn result(part: u8, answer: Option<&str>, nanos: u64, samples: u128) -> PartResult {
+        PartResult {
+            id: puzzle!(2024, 1),
+            part,
+            answer: answer.map(Into::into),
+            status: Status::Ok,
+            duration: Duration::from_nanos(nanos),
+            samples,
+            min: Duration::from_nanos(nanos),
+            median: Duration::from_nanos(nanos),
+            p95: Duration::from_nanos(nanos),
+            max: Duration::from_nanos(nanos),
+            std_dev: Duration::ZERO,
+            outliers: 0,
         }
     }
+
+    #[test]
+    fn collects_benched_parts() {
+        let res = to_timing(
+            &[
+                result(1, Some("0"), 74, 100_000),
+                result(2, Some("10"), 74_130_000, 99_999),
+            ],
+            puzzle!(2024, 1),
+        );
+        assert_eq!(res.total_nanos, 74_130_074_f64);
+        assert_eq!(res.part_1.unwrap().to_string(), "74.0ns");
+
+        let part_2 = res.part_2.unwrap();
+        assert_eq!(part_2.to_string(), "74.1ms");
+        assert_eq!(part_2.stats.unwrap().samples, 99_999);
+    }
+
+    #[test]
+    fn adds_parse_step_to_total() {
+        let res = to_timing(
+            &[result(0, None, 26, 100), result(1, Some("0"), 74, 100)],
+            puzzle!(2024, 1),
+        );
+        assert_eq!(res.total_nanos, 100_f64);
+        assert_eq!(res.part_1.unwrap().to_string(), "74.0ns");
+        assert_eq!(res.part_2.is_none(), true);
+    }
+
+    #[test]
+    fn skips_missing_and_unbenched_parts() {
+        let res = to_timing(
+            &[result(1, None, 10, 1), result(2, Some("10"), 10, 1)],
+            puzzle!(2024, 1),
+        );
+        assert_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
+
+    fn failed(part: u8, status: Status) -> PartResult {
+        PartResult {
+            status,
+            ..result(part, None, 10, 1)
+        }
+    }
+
+    #[test]
+    fn derives_day_outcomes_from_parts() {
+        let solved = result(1, Some("0"), 10, 1);
+        let unsolved = failed(2, Status::NotImplemented);
+
+        assert_eq!(
+            DayOutcome::from_parts(&[solved.clone(), unsolved.clone()]),
+            DayOutcome::Solved
+        );
+        assert_eq!(
+            DayOutcome::from_parts(&[unsolved.clone()]),
+            DayOutcome::Unsolved
+        );
+        assert_eq!(DayOutcome::from_parts(&[]), DayOutcome::Unsolved);
+        // a parse step alone does not solve a day.
+        assert_eq!(
+            DayOutcome::from_parts(&[result(0, None, 10, 1), unsolved]),
+            DayOutcome::Unsolved
+        );
+        assert_eq!(
+            DayOutcome::from_parts(&[solved.clone(), failed(2, Status::Panicked("oops".into()))]),
+            DayOutcome::Failed
+        );
+        assert_eq!(
+            DayOutcome::from_parts(&[failed(1, Status::Error("bad input".into())), solved]),
+            DayOutcome::Failed
+        );
+        assert_eq!(
+            DayOutcome::from_parts(&[
+                failed(1, Status::Panicked("oops".into())),
+                failed(2, Status::TimedOut)
+            ]),
+            DayOutcome::TimedOut
+        );
+    }
+
+    #[test]
+    fn finishes_parallel_days_in_order() {
+        let ids: Vec<_> = all_days(year!(2024))
+            .take(6)
+            .filter_map(|day| PuzzleId::new(year!(2024), day))
+            .collect();
+        let mut finished = vec![];
+
+        run_parallel(
+            &ids,
+            3,
+            |id, buffer| {
+                // later days finish first.
+                let day = u64::from(id.day.into_inner());
+                thread::sleep(Duration::from_millis(60 - 10 * day));
+                buffer.stdout = id.day.to_string();
+                vec![result(1, Some("0"), day, 1)]
+            },
+            |index, results, buffer| finished.push((index, results.len(), buffer.stdout)),
+        );
+
+        assert_eq!(
+            finished,
+            (0..6)
+                .map(|index| (index, 1, format!("{:02}", index + 1)))
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -1,171 +1,1409 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::ffi::OsString;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::io::{self, Write, stdout};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
+
+use tinyjson::JsonValue;
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Day, PuzzleId, Solution, Year,
+    answers::Answers,
+    aoc_client::{self, AocClientError},
+    fetch_log, read_file,
+    submissions::{Outcome, Submissions},
+};
+
+/// The part number of the results of a solution's parse step, see [`execute_parse`].
+pub const PARSE_STEP: u8 = 0;
+
+/// The outcome of running a single solution part.
+/// In [`OutputFormat::Json`], each result is printed as one JSON record per line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub id: PuzzleId,
+    /// The part number, or [`PARSE_STEP`] for the parse step of solutions that declare one.
+    pub part: u8,
+    /// The displayed answer, `None` if the part is not solved.
+    pub answer: Option<String>,
+    /// Why the part has no answer, or [`Status::Ok`].
+    pub status: Status,
+    /// The execution time, averaged over all samples that are not outliers.
+    pub duration: Duration,
+    pub samples: u128,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    /// The standard deviation of all samples that are not outliers.
+    pub std_dev: Duration,
+    /// The number of samples outside of the Tukey fences, see [`Stats`].
+    pub outliers: u128,
+}
+
+impl PartResult {
+    #[must_use]
+    pub fn new(
+        id: PuzzleId,
+        part: u8,
+        answer: Option<String>,
+        status: Status,
+        stats: Stats,
+    ) -> Self {
+        PartResult {
+            id,
+            part,
+            answer,
+            status,
+            duration: stats.mean,
+            samples: stats.samples,
+            min: stats.min,
+            median: stats.median,
+            p95: stats.p95,
+            max: stats.max,
+            std_dev: stats.std_dev,
+            outliers: stats.outliers,
+        }
+    }
+
+    /// The execution time statistics of this part.
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        Stats {
+            samples: self.samples,
+            mean: self.duration,
+            min: self.min,
+            median: self.median,
+            p95: self.p95,
+            max: self.max,
+            std_dev: self.std_dev,
+            outliers: self.outliers,
+        }
+    }
+}
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Whether a solution part completed, and why it has no answer if it did not.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer, or the parse step completed.
+    #[default]
+    Ok,
+    /// The part returned `None`.
+    NotImplemented,
+    /// The part returned an error. Contains the error and its chain of sources.
+    Error(String),
+    /// The part panicked. Contains the panic message.
+    Panicked(String),
+    /// The part ran longer than the timeout set with `--timeout <duration>`.
+    TimedOut,
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+impl Status {
+    /// The name of the status in machine-readable output.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::NotImplemented => "not implemented",
+            Status::Error(_) => "error",
+            Status::Panicked(_) => "panicked",
+            Status::TimedOut => "timed out",
+        }
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    /// The error or panic message, if any.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Status::Error(message) | Status::Panicked(message) => Some(message),
+            Status::Ok | Status::NotImplemented | Status::TimedOut => None,
+        }
+    }
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.message() {
+            Some(message) => write!(f, "{}: {message}", self.name()),
+            None => write!(f, "{}", self.name()),
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// The return value of a solution part: `Option<T>`, where `None` means that the part is not implemented,
+/// or `Result<T, E>` for any error that converts into an [`anyhow::Error`], e.g. `anyhow::Result<T>`.
+pub trait PartOutput {
+    /// The displayed answer, or the status explaining why there is none.
+    fn into_answer(self) -> Result<String, Status>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<String, Status> {
+        self.map(|x| x.to_string()).ok_or(Status::NotImplemented)
+    }
+}
+
+impl<T: Display, E: Into<anyhow::Error>> PartOutput for Result<T, E> {
+    fn into_answer(self) -> Result<String, Status> {
+        // the alternate format of `anyhow::Error` includes the chain of sources.
+        self.map(|x| x.to_string())
+            .map_err(|e| Status::Error(format!("{:#}", e.into())))
+    }
+}
+
+/// Controls how results of solution parts are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Human,
+    /// One JSON record per part and line, see [`PartResult`].
+    Json,
+}
+
+/// Controls how a solution part is benched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// The approximate execution time spent on measured samples.
+    /// A tenth of it is additionally spent on warm-up runs.
+    pub budget: Duration,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Bounds of the number of measured samples when benching.
+const MIN_SAMPLES: u128 = 10;
+const MAX_SAMPLES: u128 = 10_000;
+
+/// Options of a solution binary. The `main` generated by [`solution!`](crate::solution) parses them
+/// once from the command line and passes them to each part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Bench each part, set with `--time` and optionally `--budget <duration>`.
+    pub bench: Option<BenchOptions>,
+    /// The part to submit, set with `--submit <part>`.
+    pub submit: Option<u8>,
+    /// Read the input from this path instead of `data/<year>/inputs/<day>.txt`, set with `--input <path>`.
+    /// The path `-` reads the input from stdin.
+    pub input: Option<PathBuf>,
+    /// Set with `--format <human|json>`.
+    pub format: OutputFormat,
+    /// The wall-clock time a part may run before it is reported as timed out, set with `--timeout <duration>`.
+    /// As a running part can't be stopped, the binary exits after reporting it. Benching is not limited.
+    pub timeout: Option<Duration>,
+    /// Only run this part, set with `--part <1|2>`.
+    pub part: Option<u8>,
+}
+
+impl RunConfig {
+    /// Parse the config from the arguments of the current process. Exits on invalid input.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_args(env::args_os().skip(1).collect()).unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input: {e}");
+            process::exit(1);
+        })
+    }
+
+    /// Parse the config from command-line arguments, without the program name.
+    /// Arguments that are not options of the runner are ignored.
+    pub fn from_args(args: Vec<OsString>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_vec(args);
+
+        let is_timed = args.contains("--time");
+        let budget = args.opt_value_from_fn("--budget", parse_duration)?;
+
+        let config = RunConfig {
+            bench: is_timed.then(|| {
+                budget.map_or_else(BenchOptions::default, |budget| BenchOptions { budget })
+            }),
+            submit: args.opt_value_from_fn("--submit", parse_part)?,
+            input: args
+                .opt_value_from_os_str("--input", |x| Ok::<_, Infallible>(PathBuf::from(x)))?,
+            format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+            timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            part: args.opt_value_from_fn("--part", parse_part)?,
+        };
+
+        // answers for other inputs than the real one must never be submitted.
+        if config.submit.is_some() && config.input.is_some() {
+            return Err("`--submit` can't be combined with `--input`.".into());
+        }
+
+        Ok(config)
+    }
+
+    /// Read the puzzle input, either from `--input` or from the data directory. Exits if it can't be read.
+    #[must_use]
+    pub fn read_input(&self, id: PuzzleId) -> String {
+        let Some(path) = &self.input else {
+            return read_file("inputs", id);
+        };
+
+        let result = if path.as_os_str() == "-" {
+            io::read_to_string(io::stdin())
+        } else {
+            fs::read_to_string(path)
+        };
+
+        result.unwrap_or_else(|e| {
+            eprintln!("Failed to read input {path:?}: {e}");
+            process::exit(1);
+        })
+    }
+}
+
+/// Parse a part number, which is either 1 or 2.
+pub fn parse_part(s: &str) -> Result<u8, &'static str> {
+    match s.parse() {
+        Ok(part @ (1 | 2)) => Ok(part),
+        _ => Err("expecting part 1 or 2"),
+    }
+}
+
+/// Run a solution part as selected by `config`. Parts other than the one passed with `--part` are skipped.
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+    id: PuzzleId,
+    part: u8,
+    config: &RunConfig,
+) {
+    if config.part.is_some_and(|x| x != part) {
+        return;
+    }
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+    let result = execute_part(
+        func,
+        input,
+        id,
+        part,
+        config.bench,
+        config.format,
+        config.timeout,
+    );
+
+    if let Some(answer) = result.answer {
+        match submit_result(&answer, id, part, config.submit) {
+            Some(Ok(())) => {
+                println!("🎄 That's the right answer!");
+                record_answer(id, part, &answer);
+            }
+            Some(Err(e)) => {
+                eprintln!("Submission failed: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+}
 
-        func(input)
+/// Run a [`Solution`] against the input selected by `config`, print its results, and submit an answer if
+/// requested. This is the `main` of solution binaries that implement the trait.
+pub fn run_solution<S: Solution>(config: &RunConfig) {
+    let input = config.read_input(S::PUZZLE);
+    let (Some(parsed), _) = parse_input::<S>(&input, config.bench, config.format) else {
+        process::exit(1);
     };
-    let base_time = timer.elapsed();
 
-    hook(&result);
+    if S::PARTS.contains(&1) {
+        run_part(S::part_one, &parsed, S::PUZZLE, 1, config);
+    }
+    if S::PARTS.contains(&2) {
+        run_part(S::part_two, &parsed, S::PUZZLE, 2, config);
+    }
+}
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+/// Run a [`Solution`] against an input and print its results, see [`RunFn`](super::registry::RunFn).
+#[must_use]
+pub fn execute_solution<S: Solution>(
+    input: &str,
+    bench: Option<BenchOptions>,
+    format: OutputFormat,
+    part: Option<u8>,
+) -> Vec<PartResult> {
+    let (parsed, parse_result) = parse_input::<S>(input, bench, format);
+
+    let mut results: Vec<_> = parse_result.into_iter().collect();
+    let Some(parsed) = parsed else {
+        return results;
+    };
+
+    let is_selected = |x: u8| S::PARTS.contains(&x) && part.is_none_or(|part| part == x);
+
+    if is_selected(1) {
+        results.push(execute_part(
+            S::part_one,
+            &parsed,
+            S::PUZZLE,
+            1,
+            bench,
+            format,
+            None,
+        ));
+    }
+    if is_selected(2) {
+        results.push(execute_part(
+            S::part_two,
+            &parsed,
+            S::PUZZLE,
+            2,
+            bench,
+            format,
+            None,
+        ));
+    }
+    results
+}
+
+/// Solve the parts of a [`Solution`] without printing anything, see [`AnswersFn`](super::registry::AnswersFn).
+/// A part that panics is reported as panicked and does not stop the other part. If parsing panics,
+/// every part is.
+#[must_use]
+pub fn solve<S: Solution>(input: &str) -> Vec<(u8, Result<String, Status>)> {
+    let panicked = |payload: Box<dyn Any + Send>| Status::Panicked(panic_message(payload.as_ref()));
+
+    let parsed = match panic::catch_unwind(AssertUnwindSafe(|| S::parse(input))) {
+        Ok(parsed) => Ok(parsed),
+        Err(payload) => Err(panicked(payload)),
+    };
+
+    let solve_part = |part: u8| {
+        let parsed = parsed.as_ref().map_err(Clone::clone)?;
+        panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => S::part_one(parsed).into_answer(),
+            _ => S::part_two(parsed).into_answer(),
+        }))
+        .unwrap_or_else(|payload| Err(panicked(payload)))
+    };
+
+    S::PARTS
+        .iter()
+        .filter(|part| matches!(part, 1 | 2))
+        .map(|part| (*part, solve_part(*part)))
+        .collect()
+}
+
+/// Parse the input of a [`Solution`], timing the parse step if it has one.
+/// The parsed input is `None` if parsing panicked.
+fn parse_input<'a, S: Solution>(
+    input: &'a str,
+    bench: Option<BenchOptions>,
+    format: OutputFormat,
+) -> (Option<S::Input<'a>>, Option<PartResult>) {
+    if S::HAS_PARSE_STEP {
+        let (parsed, result) = execute_parse(S::parse, input, S::PUZZLE, bench, format);
+        (parsed, Some(result))
     } else {
-        (base_time, 1)
+        match run_once(&S::parse, input) {
+            (Ok(parsed), _) => (Some(parsed), None),
+            // report the panic as a parse step, as no part can run.
+            (Err(payload), base_time) => {
+                let result = parse_panicked(S::PUZZLE, payload.as_ref(), base_time, format);
+                (None, Some(result))
+            }
+        }
+    }
+}
+
+/// Record an accepted answer, so that `cargo verify` can check it later.
+fn record_answer(id: PuzzleId, part: u8, answer: &str) {
+    match Answers::record(id, part, answer) {
+        Ok(()) => println!("🎄 Recorded accepted answer in {:?}.", Answers::path(id)),
+        Err(e) => eprintln!("Failed to record accepted answer: {e}"),
+    }
+}
+
+/// Run a solution part and print its answer and timing.
+/// This is used both by solution binaries and by in-process runs of the [`registry`](super::registry).
+/// Parts that do not return an answer are not benched.
+///
+/// If a `timeout` is passed and the first execution of the part exceeds it, the part is reported as
+/// timed out and the process exits. Runs within a process that must outlive the part pass `None`.
+pub fn execute_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    id: PuzzleId,
+    part: u8,
+    bench: Option<BenchOptions>,
+    format: OutputFormat,
+    timeout: Option<Duration>,
+) -> PartResult {
+    let watchdog = timeout.map(|timeout| Watchdog::arm(id, part, timeout, format));
+    let (result, base_time) = run_once(&func, input.clone());
+    drop(watchdog);
+
+    let answer = match result {
+        Ok(output) => output.into_answer(),
+        Err(payload) => Err(Status::Panicked(panic_message(payload.as_ref()))),
+    };
+
+    let bench = bench.filter(|_| answer.is_ok());
+
+    if format == OutputFormat::Human {
+        print!(
+            "{}",
+            format_result(answer.as_deref(), &format!("Part {part}"), "")
+        );
+
+        if bench.is_some() {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+            let _ = stdout().flush();
+        }
+    }
+
+    let stats = match bench {
+        Some(options) => run_bench(func, input, options),
+        None => Stats::from_samples(&[base_time]),
+    };
+
+    let result = match answer {
+        Ok(answer) => PartResult::new(id, part, Some(answer), Status::Ok, stats),
+        Err(status) => PartResult::new(id, part, None, status, stats),
     };
 
-    (result, run.0, run.1)
+    print_part_result(&result, format);
+    result
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Run the parse step of a solution and print its timing.
+/// Returns the parsed input, which is shared by all parts, and the timing as a result of [`PARSE_STEP`].
+/// If parsing panics, there is no parsed input and the parts can't run.
+pub fn execute_parse<'a, T>(
+    func: impl Fn(&'a str) -> T,
+    input: &'a str,
+    id: PuzzleId,
+    bench: Option<BenchOptions>,
+    format: OutputFormat,
+) -> (Option<T>, PartResult) {
+    let (result, base_time) = run_once(&func, input);
+
+    let parsed = match result {
+        Ok(parsed) => parsed,
+        Err(payload) => {
+            return (
+                None,
+                parse_panicked(id, payload.as_ref(), base_time, format),
+            );
+        }
+    };
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if format == OutputFormat::Human && bench.is_some() {
+        print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let stats = match bench {
+        Some(options) => run_bench(func, input, options),
+        None => Stats::from_samples(&[base_time]),
+    };
+
+    let result = PartResult::new(id, PARSE_STEP, None, Status::Ok, stats);
+
+    print_part_result(&result, format);
+    (Some(parsed), result)
+}
+
+/// Print and return the result of a parse step that panicked.
+fn parse_panicked(
+    id: PuzzleId,
+    payload: &(dyn Any + Send),
+    duration: Duration,
+    format: OutputFormat,
+) -> PartResult {
+    let status = Status::Panicked(panic_message(payload));
+    let result = PartResult::new(
+        id,
+        PARSE_STEP,
+        None,
+        status,
+        Stats::from_samples(&[duration]),
+    );
+
+    print_part_result(&result, format);
+    result
+}
+
+/// Print the final output for a solution part.
+pub fn print_part_result(result: &PartResult, format: OutputFormat) {
+    let str = format_part_result(result, format);
+
+    match format {
+        // replaces the intermediate result that was printed to the same line.
+        OutputFormat::Human => println!("\r{str}"),
+        OutputFormat::Json => println!("{str}"),
+    }
+}
+
+/// Format the final output for a solution part, without a trailing line break.
+#[must_use]
+pub fn format_part_result(result: &PartResult, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Human if result.part == PARSE_STEP => match result.status.message() {
+            Some(message) => format!("Parse: ✖ {ANSI_BOLD}panicked:{ANSI_RESET} {message}"),
+            None => format!("Parse:{}", format_duration(result)),
+        },
+        OutputFormat::Human => format_result(
+            result.answer.as_deref().ok_or(&result.status),
+            &format!("Part {}", result.part),
+            &format_duration(result),
+        ),
+        // NOTE: stringifying a JSON value without non-finite numbers cannot fail.
+        OutputFormat::Json => JsonValue::from(result).stringify().unwrap(),
+    }
+}
+
+/// Statistics over the execution times of a solution part.
+///
+/// Samples outside of the Tukey fences (1.5 times the interquartile range below the first or above the
+/// third quartile) are counted as outliers and do not contribute to the mean and the standard deviation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics over a set of execution times.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let fence = (q3 - q1).mul_f64(1.5);
+        let lower_fence = q1.saturating_sub(fence);
+        let upper_fence = q3 + fence;
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .filter(|x| **x >= lower_fence && **x <= upper_fence)
+            .map(Duration::as_secs_f64)
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let count = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / count;
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1_f64)
+        } else {
+            0_f64
+        };
+
+        Self {
+            samples: sorted.len() as u128,
+            mean: Duration::from_secs_f64(mean),
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            p95: percentile(&sorted, 95),
+            max: sorted[sorted.len() - 1],
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: (sorted.len() - kept.len()) as u128,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * sorted.len()).div_ceil(100);
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Run a solution part once and measure its execution time. A panic of the part is caught and returned.
+/// When benched, [`run_bench`] measures the part afterwards.
+fn run_once<I, T>(func: &impl Fn(I) -> T, input: I) -> (thread::Result<T>, Duration) {
+    let timer = Instant::now();
+    let result = {
+        #[cfg(feature = "dhat-heap")]
+        let _profiler = dhat::Profiler::new_heap();
+
+        panic::catch_unwind(AssertUnwindSafe(|| func(input)))
+    };
+
+    (result, timer.elapsed())
+}
+
+/// Ends the process if a part runs longer than its timeout, after reporting the part as timed out.
+/// A running part can't be stopped otherwise. Dropping the watchdog disarms it.
+struct Watchdog {
+    _disarm: mpsc::Sender<()>,
+}
 
-    let mut timers: Vec<Duration> = vec![];
+impl Watchdog {
+    fn arm(id: PuzzleId, part: u8, timeout: Duration, format: OutputFormat) -> Self {
+        let (disarm, disarmed) = mpsc::channel::<()>();
 
-    for _ in 0..bench_iterations {
+        thread::spawn(move || {
+            // the sender is never used, the channel disconnects when the watchdog is dropped.
+            if disarmed.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+                let stats = Stats::from_samples(&[timeout]);
+                let result = PartResult::new(id, part, None, Status::TimedOut, stats);
+                print_part_result(&result, format);
+                process::exit(1);
+            }
+        });
+
+        Watchdog { _disarm: disarm }
+    }
+}
+
+/// The message of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Bench a solution part: it is warmed up and then measured (approx. the time budget or 10 samples, whatever take longer.)
+fn run_bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, options: BenchOptions) -> Stats {
+    let run = || {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        timer.elapsed()
+    };
+
+    // warm up caches and branch predictors, and estimate the execution time.
+    let warm_up_budget = options.budget / 10;
+    let warm_up_timer = Instant::now();
+    let mut warm_up_runs = 0_u128;
+    let mut warm_up_time = Duration::ZERO;
+
+    while warm_up_runs == 0
+        || (warm_up_timer.elapsed() < warm_up_budget && warm_up_runs < MAX_SAMPLES)
+    {
+        warm_up_time += run();
+        warm_up_runs += 1;
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    let estimate = warm_up_time.as_nanos() / warm_up_runs;
+    let bench_iterations =
+        (options.budget.as_nanos() / cmp::max(estimate, 10)).clamp(MIN_SAMPLES, MAX_SAMPLES);
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    let timers: Vec<Duration> = (0..bench_iterations).map(|_| run()).collect();
+    Stats::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(result: &PartResult) -> String {
+    let duration = result.duration;
+    let samples = result.samples;
+
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({duration:.1?} @ {samples} samples) {ANSI_ITALIC}[min {:.1?}, median {:.1?}, p95 {:.1?}, σ {:.1?}, {} outliers]{ANSI_RESET}",
+            result.min, result.median, result.p95, result.std_dev, result.outliers
+        )
+    }
+}
+
+/// Parse a duration like `500ms`, `1.5s`, `250µs` or `100ns`. Values without a unit are read as milliseconds.
+pub fn parse_duration(s: &str) -> Result<Duration, ParseDurationError> {
+    let s = s.trim();
+    let split_at = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split_at);
+
+    let value: f64 = value.parse().map_err(|_| ParseDurationError)?;
+
+    let secs = match unit.trim() {
+        "ns" => value / 1e9,
+        "µs" | "us" => value / 1e6,
+        "" | "ms" => value / 1e3,
+        "s" => value,
+        _ => return Err(ParseDurationError),
+    };
+
+    Duration::try_from_secs_f64(secs).map_err(|_| ParseDurationError)
+}
+
+/// An error which can be returned when parsing a duration with [`parse_duration`].
+#[derive(Debug)]
+pub struct ParseDurationError;
+
+impl std::error::Error for ParseDurationError {}
+
+impl Display for ParseDurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a duration like `500ms` or `2s`")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Format the human-readable output of a part. Without a duration, this is the intermediate result that is
+/// shown while the part is benched.
+fn format_result(result: Result<&str, &Status>, part: &str, duration_str: &str) -> String {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+        Ok(result) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    str
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    format!("{str}\n{result}")
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}")
             }
         }
-        None => {
+        Err(status) => {
+            let str = match status {
+                Status::Error(message) => {
+                    format!("{part}: ✖ {ANSI_BOLD}error:{ANSI_RESET} {message}")
+                }
+                Status::Panicked(message) => {
+                    format!("{part}: ✖ {ANSI_BOLD}panicked:{ANSI_RESET} {message}")
+                }
+                Status::TimedOut => {
+                    format!("{part}: ✖ {ANSI_BOLD}timed out{ANSI_RESET}{duration_str}")
+                }
+                Status::Ok | Status::NotImplemented => format!("{part}: ✖"),
+            };
+
             if is_intermediate_result {
-                print!("{part}: ✖");
+                str
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!("{str}             ")
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => f.write_str("human"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl std::error::Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of `human` or `json`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos_to_json(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_from_json(value: &JsonValue) -> Option<Duration> {
+    let nanos = *value.get::<f64>()?;
+    (nanos >= 0_f64).then(|| Duration::from_nanos(nanos as u64))
+}
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("duration_nanos".into(), nanos_to_json(value.mean));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("min_nanos".into(), nanos_to_json(value.min));
+        map.insert("median_nanos".into(), nanos_to_json(value.median));
+        map.insert("p95_nanos".into(), nanos_to_json(value.p95));
+        map.insert("max_nanos".into(), nanos_to_json(value.max));
+        map.insert("std_dev_nanos".into(), nanos_to_json(value.std_dev));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let mean = json
+            .get("duration_nanos")
+            .and_then(nanos_from_json)
+            .ok_or("Expected duration_nanos to be a positive number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .filter(|samples| **samples >= 1_f64)
+            .map(|samples| *samples as u128)
+            .ok_or("Expected samples to be a positive number.")?;
+
+        let min = json
+            .get("min_nanos")
+            .and_then(nanos_from_json)
+            .ok_or("Expected min_nanos to be a positive number.")?;
+
+        let median = json
+            .get("median_nanos")
+            .and_then(nanos_from_json)
+            .ok_or("Expected median_nanos to be a positive number.")?;
+
+        let p95 = json
+            .get("p95_nanos")
+            .and_then(nanos_from_json)
+            .ok_or("Expected p95_nanos to be a positive number.")?;
+
+        let max = json
+            .get("max_nanos")
+            .and_then(nanos_from_json)
+            .ok_or("Expected max_nanos to be a positive number.")?;
+
+        let std_dev = json
+            .get("std_dev_nanos")
+            .and_then(nanos_from_json)
+            .ok_or("Expected std_dev_nanos to be a positive number.")?;
+
+        let outliers = json
+            .get("outliers")
+            .and_then(|v| v.get::<f64>())
+            .filter(|outliers| **outliers >= 0_f64)
+            .map(|outliers| *outliers as u128)
+            .ok_or("Expected outliers to be a positive number.")?;
+
+        Ok(Stats {
+            samples,
+            mean,
+            min,
+            median,
+            p95,
+            max,
+            std_dev,
+            outliers,
+        })
+    }
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map = match JsonValue::from(&value.stats()) {
+            JsonValue::Object(map) => map,
+            _ => HashMap::new(),
+        };
+
+        map.insert("year".into(), JsonValue::String(value.id.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.id.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.name().to_string()),
+        );
+        map.insert(
+            "error".into(),
+            match value.status.message() {
+                Some(x) => JsonValue::String(x.to_string()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected result.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected result.day to be a Day struct.")?;
+
+        let id = PuzzleId::new(year, day)
+            .ok_or("Expected result.day to be part of the calendar of result.year.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| [0_f64, 1_f64, 2_f64].contains(part))
+            .map(|part| *part as u8)
+            .ok_or("Expected result.part to be 0, 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let error = json
+            .get("error")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .unwrap_or_default();
+
+        // results written before the status was recorded only distinguish answered and unanswered parts.
+        let status = match json.get("status").map(|v| v.get::<String>()) {
+            None if answer.is_some() || part == PARSE_STEP => Status::Ok,
+            None => Status::NotImplemented,
+            Some(Some(status)) => match status.as_str() {
+                "ok" => Status::Ok,
+                "not implemented" => Status::NotImplemented,
+                "error" => Status::Error(error),
+                "panicked" => Status::Panicked(error),
+                "timed out" => Status::TimedOut,
+                _ => return Err(format!("Unknown result.status `{status}`.")),
+            },
+            Some(None) => return Err("Expected result.status to be a string.".into()),
+        };
+
+        let stats = Stats::try_from(value).map_err(|e| format!("Invalid result: {e}"))?;
+
+        Ok(PartResult::new(id, part, answer.cloned(), status, stats))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Try to submit one part of the solution if it was requested with `--submit <part>`.
+/// Answers that earlier submissions have shown to be wrong are not submitted.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    id: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+    submit_part: Option<u8>,
+) -> Option<Result<(), AocClientError>> {
+    if submit_part != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let answer = result.to_string();
+
+    let mut submissions = Submissions::read(id).unwrap_or_else(|e| {
+        eprintln!("Failed to read {:?}: {e}", Submissions::path(id));
+        process::exit(1);
+    });
+
+    // don't spend a submission (and its timeout) on an answer that is known to be wrong.
+    if let Err(blocked) = submissions.check(part, &answer) {
+        eprintln!("Not submitting {answer}: {blocked}");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result...");
+    let result = aoc_client::submit(id, part, &answer);
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if let Some(outcome) = Outcome::from_result(&result) {
+        submissions.record(part, &answer, outcome, fetch_log::now());
+        if let Err(e) = submissions.store(id) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
+
+    Some(result)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{
+        BenchOptions, OutputFormat, PARSE_STEP, PartOutput, PartResult, RunConfig, Stats, Status,
+        execute_part, execute_solution, parse_duration, solve,
+    };
+    use crate::template::{
+        DaySelection, PuzzleId, Solution,
+        answers::Answers,
+        example_part_one, example_part_two, read_file,
+        registry::{self, Entry},
     };
+    use crate::{day, puzzle};
 
-    if part_submit != part {
-        return None;
+    /// Sums the left and the right column of the example of 2024 day 1.
+    struct Sums;
+
+    impl Solution for Sums {
+        const PUZZLE: PuzzleId = puzzle!(2024, 1);
+        const EXPECTED: [Option<&'static str>; 2] = [Some("16"), None];
+        const HAS_PARSE_STEP: bool = false;
+
+        type Input<'a> = &'a str;
+        type Answer1 = Option<u32>;
+        type Answer2 = Result<u32, std::num::ParseIntError>;
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            input
+        }
+
+        fn part_one(input: &Self::Input<'_>) -> Self::Answer1 {
+            input
+                .lines()
+                .map(|line| line.split_whitespace().next()?.parse::<u32>().ok())
+                .sum()
+        }
+
+        fn part_two(input: &Self::Input<'_>) -> Self::Answer2 {
+            input
+                .lines()
+                .map(|line| {
+                    line.split_whitespace()
+                        .last()
+                        .unwrap_or_default()
+                        .parse::<u32>()
+                })
+                .sum()
+        }
+    }
+
+    static SOLUTIONS: &[Entry] = &[Entry::of::<Sums>()];
+
+    /// Panics in part one, and counts the lines of the input in part two.
+    struct PanicsInPartOne;
+
+    impl Solution for PanicsInPartOne {
+        const PUZZLE: PuzzleId = puzzle!(2024, 2);
+        const HAS_PARSE_STEP: bool = false;
+
+        type Input<'a> = &'a str;
+        type Answer1 = Option<u32>;
+        type Answer2 = Option<usize>;
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            input
+        }
+
+        fn part_one(_: &Self::Input<'_>) -> Self::Answer1 {
+            panic!("oops")
+        }
+
+        fn part_two(input: &Self::Input<'_>) -> Self::Answer2 {
+            Some(input.lines().count())
+        }
+    }
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50, 60, 70, 80, 90, 100]));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.mean, Duration::from_nanos(55));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 11, 12, 10, 1000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(1000));
+        assert!(stats.mean < Duration::from_nanos(12));
+    }
+
+    #[test]
+    fn computes_stats_for_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("250µs").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("100ns").unwrap(), Duration::from_nanos(100));
+        assert_eq!(parse_duration("20").unwrap(), Duration::from_millis(20));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("10m").is_err());
+    }
+
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!(
+            OutputFormat::from_str("human").unwrap(),
+            OutputFormat::Human
         );
-        process::exit(1);
+        assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+        assert!(OutputFormat::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn parses_run_config() {
+        let parse = |args: &[&str]| RunConfig::from_args(args.iter().map(Into::into).collect());
+
+        assert_eq!(parse(&[]).unwrap(), RunConfig::default());
+
+        let config = parse(&[
+            "--time", "--budget", "2s", "--input", "in.txt", "--format", "json",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.bench,
+            Some(BenchOptions {
+                budget: Duration::from_secs(2)
+            })
+        );
+        assert_eq!(config.input, Some("in.txt".into()));
+        assert_eq!(config.format, OutputFormat::Json);
+        assert_eq!(parse(&["--submit", "2"]).unwrap().submit, Some(2));
+        assert_eq!(
+            parse(&["--timeout", "10s"]).unwrap().timeout,
+            Some(Duration::from_secs(10))
+        );
+
+        // the budget alone does not enable benching.
+        assert_eq!(parse(&["--budget", "2s"]).unwrap().bench, None);
+        assert!(parse(&["--submit", "3"]).is_err());
+        assert!(parse(&["--submit"]).is_err());
+        assert!(parse(&["--submit", "1", "--input", "-"]).is_err());
+        assert!(parse(&["--timeout", "soon"]).is_err());
+    }
+
+    #[test]
+    fn serializes_results_roundtrip() {
+        let result = PartResult {
+            id: puzzle!(2024, 7),
+            part: 2,
+            answer: Some("42".into()),
+            status: Status::Ok,
+            duration: Duration::from_nanos(1500),
+            samples: 100,
+            min: Duration::from_nanos(1000),
+            median: Duration::from_nanos(1400),
+            p95: Duration::from_nanos(2500),
+            max: Duration::from_nanos(3000),
+            std_dev: Duration::from_nanos(200),
+            outliers: 3,
+        };
+
+        let json = JsonValue::from(&result).stringify().unwrap();
+        let parsed = PartResult::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+        assert_eq!(parsed, result);
+    }
+
+    #[test]
+    fn serializes_failed_results_roundtrip() {
+        let result = PartResult::new(
+            puzzle!(2024, 7),
+            1,
+            None,
+            Status::Error("invalid digit: `x` is not a number".into()),
+            Stats::from_samples(&[Duration::from_nanos(1500)]),
+        );
+
+        let json = JsonValue::from(&result).stringify().unwrap();
+        assert!(json.contains(r#""status":"error""#));
+        let parsed = PartResult::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+        assert_eq!(parsed, result);
+    }
+
+    #[test]
+    fn serializes_timed_out_results_roundtrip() {
+        let result = PartResult::new(
+            puzzle!(2024, 6),
+            2,
+            None,
+            Status::TimedOut,
+            Stats::from_samples(&[Duration::from_secs(10)]),
+        );
+
+        let json = JsonValue::from(&result).stringify().unwrap();
+        assert!(json.contains(r#""status":"timed out""#));
+        let parsed = PartResult::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+        assert_eq!(parsed, result);
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(42).into_answer(), Ok("42".into()));
+        assert_eq!(None::<u32>.into_answer(), Err(Status::NotImplemented));
+
+        let result: anyhow::Result<u32> =
+            Err(anyhow::anyhow!("invalid digit").context("failed to parse line 3"));
+        assert_eq!(
+            result.into_answer(),
+            Err(Status::Error(
+                "failed to parse line 3: invalid digit".into()
+            ))
+        );
+
+        let result: Result<u32, std::num::ParseIntError> = "x".parse();
+        assert_eq!(
+            result.into_answer(),
+            Err(Status::Error("invalid digit found in string".into()))
+        );
+    }
+
+    #[test]
+    fn catches_panics_of_parts() {
+        let result = execute_part(
+            |_: &str| -> Option<u32> { panic!("index out of bounds") },
+            "",
+            puzzle!(2024, 7),
+            1,
+            None,
+            OutputFormat::Json,
+            None,
+        );
+
+        assert_eq!(result.answer, None);
+        assert_eq!(
+            result.status,
+            Status::Panicked("index out of bounds".into())
+        );
+    }
+
+    #[test]
+    fn runs_solutions() {
+        let input = read_file("examples", Sums::PUZZLE);
+        let results = execute_solution::<Sums>(&input, None, OutputFormat::Json, None);
+
+        // a solution without a parse step has no parse timing.
+        assert!(results.iter().all(|x| x.part != PARSE_STEP));
+        assert_eq!(
+            results
+                .iter()
+                .map(|x| (x.part, x.answer.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![(1, Some("16")), (2, Some("27"))]
+        );
+
+        let results = execute_solution::<Sums>(&input, None, OutputFormat::Json, Some(2));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].part, 2);
+    }
+
+    #[test]
+    fn solves_solutions() {
+        let input = read_file("examples", Sums::PUZZLE);
+
+        assert_eq!(
+            solve::<Sums>(&input),
+            vec![(1, Ok("16".into())), (2, Ok("27".into()))]
+        );
+        assert_eq!(example_part_one::<Sums>(), Some(16));
+        assert_eq!(example_part_two::<Sums>(), Ok(27));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn solves_parts_after_a_panic() {
+        assert_eq!(
+            solve::<PanicsInPartOne>("a\nb"),
+            vec![
+                (1, Err(Status::Panicked("oops".into()))),
+                (2, Ok("2".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_expected_answers_of_solutions() {
+        registry::register(SOLUTIONS);
+
+        let answers = Answers::accepted(Sums::PUZZLE).unwrap();
+        assert_eq!(answers.part_1.as_deref(), Some("16"));
+        assert_eq!(answers.part_2, None);
+
+        let unsolved = |part| {
+            DaySelection {
+                days: Some("1".parse().unwrap()),
+                only_unsolved: true,
+                part: Some(part),
+                ..DaySelection::default()
+            }
+            .days(Sums::PUZZLE.year)
+        };
+
+        assert!(unsolved(1).is_empty());
+        assert!(unsolved(2).contains(&day!(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_results() {
+        let json = JsonValue::from_str(r#"{ "year": "2024", "day": "01", "part": 3 }"#).unwrap();
+        PartResult::try_from(&json).unwrap();
+    }
 }
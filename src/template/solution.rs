@@ -0,0 +1,85 @@
+/// The contract for a day's solution, see [`Solution`].
+use crate::template::runner::{PartOutput, Status};
+use crate::template::{PuzzleId, read_file};
+
+/// A day's solution.
+///
+/// Pass an implementing type to [`solution!`](crate::solution), e.g. `solution!(Day01)`, to generate the
+/// `main` function of the solution binary and register it for in-process runs.
+pub trait Solution {
+    /// The puzzle solved by this solution.
+    const PUZZLE: PuzzleId;
+
+    /// The title of the puzzle, e.g. `Historian Hysteria`. Shown when running multiple days.
+    const TITLE: Option<&'static str> = None;
+
+    /// The known answers for the real input of part one and two. `cargo verify` checks them for parts
+    /// that have no answer recorded in `data/<year>/answers`.
+    const EXPECTED: [Option<&'static str>; 2] = [None, None];
+
+    /// Whether [`Solution::parse`] does work that should be timed as a separate step.
+    /// Set this to `false` if the parts work on the input as-is.
+    const HAS_PARSE_STEP: bool = true;
+
+    /// The parts this solution implements, e.g. `&[1]` for a last day that has a single part.
+    /// Other parts are not run.
+    const PARTS: &'static [u8] = &[1, 2];
+
+    /// The parsed input, shared by both parts. It may borrow from the input, e.g. `&'a str`.
+    type Input<'a>;
+    /// The return type of part one: `Option<T>` or `Result<T, E>`, see [`PartOutput`].
+    type Answer1: PartOutput;
+    /// The return type of part two: `Option<T>` or `Result<T, E>`, see [`PartOutput`].
+    type Answer2: PartOutput;
+
+    fn parse(input: &str) -> Self::Input<'_>;
+
+    /// Solve part one. Returns `None` or an error if the part is not solved, see [`PartOutput`].
+    fn part_one(input: &Self::Input<'_>) -> Self::Answer1;
+
+    /// Solve part two. Returns `None` or an error if the part is not solved, see [`PartOutput`].
+    fn part_two(input: &Self::Input<'_>) -> Self::Answer2;
+}
+
+/// Solve part one of a solution for its example input, `data/<year>/examples/<day>.txt`.
+#[must_use]
+pub fn example_part_one<S: Solution>() -> S::Answer1 {
+    S::part_one(&S::parse(&read_file("examples", S::PUZZLE)))
+}
+
+/// Solve part two of a solution for its example input, `data/<year>/examples/<day>.txt`.
+#[must_use]
+pub fn example_part_two<S: Solution>() -> S::Answer2 {
+    S::part_two(&S::parse(&read_file("examples", S::PUZZLE)))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The input of a solution written as free functions: the parsed input, bound to the parts that solve it.
+///
+/// `solution!(<year>, <day>)` implements [`Solution`] with this input, since the type of the parsed
+/// input of free functions can't be named.
+pub struct BoundInput<'a>(Box<dyn Fn(u8) -> Result<String, Status> + 'a>);
+
+impl<'a> BoundInput<'a> {
+    /// Bind a parsed input to a function that solves a part of it.
+    pub fn new(solve: impl Fn(u8) -> Result<String, Status> + 'a) -> Self {
+        BoundInput(Box::new(solve))
+    }
+
+    /// Solve a part of the bound input.
+    #[must_use]
+    pub fn solve(&self, part: u8) -> BoundAnswer {
+        BoundAnswer((self.0)(part))
+    }
+}
+
+/// The answer of a part of a [`BoundInput`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundAnswer(pub Result<String, Status>);
+
+impl PartOutput for BoundAnswer {
+    fn into_answer(self) -> Result<String, Status> {
+        self.0
+    }
+}
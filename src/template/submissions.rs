@@ -0,0 +1,354 @@
+/// Module that records submitted answers and their verdicts, so that answers that are known to be
+/// wrong are not submitted again.
+use std::{collections::HashMap, fmt::Display, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    PuzzleId,
+    aoc_client::{AocClientError, Hint},
+    data_dir,
+};
+
+/// The verdict of the server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint whether the answer was too high or too low.
+    Wrong,
+}
+
+impl Outcome {
+    /// The verdict contained in the result of a submission. Errors that are not verdicts, e.g. a bad
+    /// session, yield `None`.
+    #[must_use]
+    pub fn from_result(result: &Result<(), AocClientError>) -> Option<Self> {
+        match result {
+            Ok(()) => Some(Outcome::Correct),
+            Err(AocClientError::WrongAnswer(Some(Hint::TooHigh))) => Some(Outcome::TooHigh),
+            Err(AocClientError::WrongAnswer(Some(Hint::TooLow))) => Some(Outcome::TooLow),
+            Err(AocClientError::WrongAnswer(None)) => Some(Outcome::Wrong),
+            Err(_) => None,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => f.write_str("correct"),
+            Outcome::TooHigh => f.write_str("too high"),
+            Outcome::TooLow => f.write_str("too low"),
+            Outcome::Wrong => f.write_str("wrong"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "too high" => Ok(Outcome::TooHigh),
+            "too low" => Ok(Outcome::TooLow),
+            "wrong" => Ok(Outcome::Wrong),
+            _ => Err(format!("unknown outcome `{s}`.")),
+        }
+    }
+}
+
+/// A submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+}
+
+/// Reason for not submitting an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Blocked {
+    /// The part was solved already, with the contained answer.
+    AlreadySolved(String),
+    /// The same answer was submitted before and was wrong.
+    KnownWrong(Outcome),
+    /// The answer is at or above an answer that was too high.
+    AboveBound(String),
+    /// The answer is at or below an answer that was too low.
+    BelowBound(String),
+}
+
+impl Display for Blocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blocked::AlreadySolved(answer) => {
+                write!(f, "this part was solved already with the answer {answer}.")
+            }
+            Blocked::KnownWrong(outcome) => {
+                write!(f, "this answer was submitted before and was {outcome}.")
+            }
+            Blocked::AboveBound(bound) => {
+                write!(
+                    f,
+                    "the answer {bound} was too high, so this answer is too high as well."
+                )
+            }
+            Blocked::BelowBound(bound) => {
+                write!(
+                    f,
+                    "the answer {bound} was too low, so this answer is too low as well."
+                )
+            }
+        }
+    }
+}
+
+/// The submissions of a day, stored in `data/<year>/submissions/<day>.json`.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// The path of the submissions file of a day, e.g. `data/2024/submissions/01.json`.
+    #[must_use]
+    pub fn path(id: PuzzleId) -> PathBuf {
+        data_dir(id.year)
+            .join("submissions")
+            .join(format!("{}.json", id.day))
+    }
+
+    /// Rehydrate the submissions of a day. If not present, returns no submissions.
+    pub fn read(id: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(Self::path(id)) {
+            Ok(contents) => Submissions::try_from(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the submissions of a day to its JSON file.
+    pub fn store(&self, id: PuzzleId) -> Result<(), io::Error> {
+        let path = Self::path(id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, outcome: Outcome, now: u64) {
+        self.data.push(Submission {
+            part,
+            answer: answer.into(),
+            outcome,
+            submitted_at: now,
+        });
+    }
+
+    /// Check an answer against earlier submissions of a part before submitting it.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Blocked> {
+        let submissions = self.data.iter().filter(|x| x.part == part);
+
+        for submission in submissions.clone() {
+            if submission.outcome == Outcome::Correct {
+                return Err(Blocked::AlreadySolved(submission.answer.clone()));
+            }
+            if submission.answer == answer {
+                return Err(Blocked::KnownWrong(submission.outcome));
+            }
+        }
+
+        // bounds can only be learned for numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        for submission in submissions {
+            let Ok(bound) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match submission.outcome {
+                Outcome::TooHigh if value >= bound => {
+                    return Err(Blocked::AboveBound(submission.answer.clone()));
+                }
+                Outcome::TooLow if value <= bound => {
+                    return Err(Blocked::BelowBound(submission.answer.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "submitted_at".into(),
+            JsonValue::Number(value.submitted_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1_f64 || **part == 2_f64)
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|outcome| Outcome::from_str(outcome).ok())
+            .ok_or("Expected submission.outcome to be an outcome.")?;
+
+        let submitted_at = json
+            .get("submitted_at")
+            .and_then(|v| v.get::<f64>())
+            .filter(|x| **x >= 0_f64)
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.submitted_at to be a positive number.")?;
+
+        Ok(Submission {
+            part,
+            answer: answer.clone(),
+            outcome,
+            submitted_at,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Blocked, Outcome, Submissions};
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.record(1, "100", Outcome::TooHigh, 1);
+        submissions.record(1, "20", Outcome::TooLow, 2);
+        submissions.record(1, "50", Outcome::Wrong, 3);
+        submissions.record(2, "abc", Outcome::Wrong, 4);
+        submissions
+    }
+
+    #[test]
+    fn blocks_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(1, "50"),
+            Err(Blocked::KnownWrong(Outcome::Wrong))
+        );
+        assert_eq!(
+            submissions.check(2, "abc"),
+            Err(Blocked::KnownWrong(Outcome::Wrong))
+        );
+    }
+
+    #[test]
+    fn blocks_answers_outside_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(1, "101"),
+            Err(Blocked::AboveBound("100".into()))
+        );
+        assert_eq!(
+            submissions.check(1, "-3"),
+            Err(Blocked::BelowBound("20".into()))
+        );
+        assert_eq!(submissions.check(1, "21"), Ok(()));
+        assert_eq!(submissions.check(1, "99"), Ok(()));
+        assert_eq!(submissions.check(2, "100"), Ok(()));
+    }
+
+    #[test]
+    fn blocks_solved_parts() {
+        let mut submissions = get_mock_submissions();
+        submissions.record(1, "42", Outcome::Correct, 5);
+        assert_eq!(
+            submissions.check(1, "42"),
+            Err(Blocked::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn serializes_submissions() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap().data, submissions.data);
+    }
+}
@@ -1,19 +1,87 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    Day, PuzzleId, Year,
+    runner::{ParseDurationError, Stats, parse_duration},
+};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static BASELINES_DIR_PATH: &str = "./data/baselines";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub id: PuzzleId,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// The benchmark time of a part, if it was benched.
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The benchmark time of a part in nanoseconds, if it was benched.
+    #[must_use]
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        self.part(part)
+            .map(|timing| timing.duration.as_secs_f64() * 1_000_000_000_f64)
+    }
+}
+
+/// Benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// The execution time, averaged over all samples that are not outliers.
+    pub duration: Duration,
+    /// Statistics of the benchmark. Not known for timings stored as display strings by older versions.
+    pub stats: Option<Stats>,
+}
+
+impl From<Stats> for PartTiming {
+    fn from(stats: Stats) -> Self {
+        PartTiming {
+            duration: stats.mean,
+            stats: Some(stats),
+        }
+    }
+}
+
+/// Parses the display strings stored by older versions, e.g. `"74.1ms"`.
+impl FromStr for PartTiming {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PartTiming {
+            duration: parse_duration(s)?,
+            stats: None,
+        })
+    }
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1?}", self.duration)
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -24,17 +92,53 @@ pub struct Timings {
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+        self.store_to(Path::new(TIMINGS_FILE_PATH))
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
+    /// Exits if the file can't be read or parsed, so that it is not overwritten with fewer timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        let path = Path::new(TIMINGS_FILE_PATH);
+
+        if !path.exists() {
+            return Timings::default();
+        }
+
+        match Self::read_from(path) {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("Failed to read timings from {TIMINGS_FILE_PATH:?}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    /// Dehydrate timings to the JSON file of a named baseline.
+    pub fn store_baseline(&self, name: &str) -> Result<(), Error> {
+        fs::create_dir_all(BASELINES_DIR_PATH)?;
+        self.store_to(&Self::baseline_path(name))
+    }
+
+    /// Rehydrate timings from the JSON file of a named baseline.
+    pub fn read_baseline(name: &str) -> Result<Self, String> {
+        Self::read_from(&Self::baseline_path(name))
+    }
+
+    /// The path of a named baseline's JSON file, e.g. `data/baselines/main.json`.
+    pub fn baseline_path(name: &str) -> PathBuf {
+        Path::new(BASELINES_DIR_PATH).join(format!("{name}.json"))
+    }
+
+    fn store_to(&self, path: &Path) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    fn read_from(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
-            .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -46,12 +150,12 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.id == timing.id) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.id);
         Timings { data }
     }
 
@@ -60,10 +164,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_complete(&self, id: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.id == id && t.part_1.is_some() && t.part_2.is_some())
     }
 }
 
@@ -111,11 +215,12 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("year".into(), JsonValue::String(value.id.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.id.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
 
         map.insert(
             "part_1".into(),
@@ -145,6 +250,16 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
+        // NOTE: timings stored before multiple years were supported have no year, they belong to `AOC_YEAR`.
+        let year = match json.get("year") {
+            Some(year) => year
+                .get::<String>()
+                .and_then(|year| Year::from_str(year).ok())
+                .ok_or("Expected timing.year to be a Year struct.")?,
+            None => Year::from_env()
+                .ok_or("Expected timing.year to be set, or `AOC_YEAR` to be a valid year.")?,
+        };
+
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
@@ -153,13 +268,15 @@ impl TryFrom<&JsonValue> for Timing {
 
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .ok_or("Expected timing.part_1 to be present.")
+            .and_then(part_from_json)
+            .map_err(|e| format!("timing.part_1: {e}"))?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .ok_or("Expected timing.part_2 to be present.")
+            .and_then(part_from_json)
+            .map_err(|e| format!("timing.part_2: {e}"))?;
 
         let total_nanos = json
             .get("total_nanos")
@@ -167,19 +284,79 @@ impl TryFrom<&JsonValue> for Timing {
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            id: PuzzleId::new(year, day)
+                .ok_or("Expected timing.day to be part of the calendar of timing.year.")?,
+            part_1,
+            part_2,
             total_nanos,
         })
     }
 }
 
+/// Read the timing of a part. Timings stored by older versions are display strings like `"74.1ms"`
+/// and are migrated to a [`PartTiming`] without statistics.
+fn part_from_json(value: &JsonValue) -> Result<Option<PartTiming>, &'static str> {
+    match value {
+        JsonValue::Null => Ok(None),
+        JsonValue::String(legacy) => PartTiming::from_str(legacy)
+            .map(Some)
+            .or(Err("Expected a legacy timing to be a duration string.")),
+        JsonValue::Object(_) => PartTiming::try_from(value).map(Some),
+        _ => Err("Expected null, a string or an object."),
+    }
+}
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        match &value.stats {
+            Some(stats) => JsonValue::from(stats),
+            None => {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                #[allow(clippy::cast_precision_loss)]
+                map.insert(
+                    "duration_nanos".into(),
+                    JsonValue::Number(value.duration.as_nanos() as f64),
+                );
+                JsonValue::Object(map)
+            }
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = &'static str;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        // timings without samples were migrated from display strings.
+        if json.contains_key("samples") {
+            let stats = Stats::try_from(value).or(Err("Expected part timing to contain stats."))?;
+            return Ok(PartTiming::from(stats));
+        }
+
+        let duration = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>())
+            .filter(|nanos| **nanos >= 0_f64)
+            .map(|nanos| Duration::from_nanos(*nanos as u64))
+            .ok_or("Expected part timing.duration_nanos to be a positive number.")?;
+
+        Ok(PartTiming {
+            duration,
+            stats: None,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::puzzle;
 
     use super::{Timing, Timings};
 
@@ -187,20 +364,20 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    id: puzzle!(2024, 1),
+                    part_1: "10ms".parse().ok(),
+                    part_2: "20ms".parse().ok(),
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    id: puzzle!(2024, 2),
+                    part_1: "30ms".parse().ok(),
+                    part_2: "40ms".parse().ok(),
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
+                    id: puzzle!(2024, 4),
+                    part_1: "40ms".parse().ok(),
                     part_2: None,
                     total_nanos: 4e+10,
                 },
@@ -209,7 +386,7 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{puzzle, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
@@ -217,12 +394,28 @@ mod tests {
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.id, puzzle!(2024, 1));
+            assert_eq!(timing.part_1, "1ms".parse().ok());
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_numeric_timings() {
+            let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": { "duration_nanos": 74130000, "samples": 100, "min_nanos": 70000000, "median_nanos": 74000000, "p95_nanos": 79000000, "max_nanos": 80000000, "std_dev_nanos": 100000, "outliers": 2 }, "part_2": { "duration_nanos": 1000 }, "total_nanos": 74131000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.duration.as_nanos(), 74_130_000);
+            assert_eq!(part_1.stats.unwrap().samples, 100);
+            assert_eq!(part_1.stats.unwrap().outliers, 2);
+
+            let part_2 = timing.part_2.as_ref().unwrap();
+            assert_eq!(part_2.duration.as_nanos(), 1000);
+            assert_eq!(part_2.stats.is_none(), true);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -240,7 +433,7 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "data": [{ "year": "2024", "day": "01" }, { "year": "2024", "day": "26" }, { "year": "2024", "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
@@ -268,9 +461,27 @@ mod tests {
         }
     }
 
-    mod is_day_complete {
+    mod migration {
+        use crate::template::timings::Timings;
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn stores_legacy_timings_as_numbers() {
+            let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": "74.1ms", "part_2": null, "total_nanos": 74100000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+
+            let migrated = JsonValue::from(timings).stringify().unwrap();
+            assert!(migrated.contains(r#""part_1":{"duration_nanos":74100000}"#));
+
+            let timings = Timings::try_from(migrated).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.duration.as_nanos(), 74_100_000);
+        }
+    }
+
+    mod is_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -278,48 +489,79 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    id: puzzle!(2024, 1),
+                    part_1: "1ms".parse().ok(),
+                    part_2: "2ms".parse().ok(),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert!(timings.is_day_complete(day!(1)));
+            assert!(timings.is_complete(puzzle!(2024, 1)));
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
+                    id: puzzle!(2024, 1),
+                    part_1: "1ms".parse().ok(),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert!(!timings.is_day_complete(day!(1)));
+            assert!(!timings.is_complete(puzzle!(2024, 1)));
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    id: puzzle!(2024, 1),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert!(!timings.is_day_complete(day!(1)));
+            assert!(!timings.is_complete(puzzle!(2024, 1)));
+        }
+    }
+
+    mod part_nanos {
+        use crate::{puzzle, template::timings::Timing};
+
+        #[test]
+        fn parses_stored_durations() {
+            let timing = Timing {
+                id: puzzle!(2024, 1),
+                part_1: "74.1ms".parse().ok(),
+                part_2: "3.0µs".parse().ok(),
+                total_nanos: 0_f64,
+            };
+
+            assert_eq!(timing.part_nanos(1).unwrap().round(), 74_100_000_f64);
+            assert_eq!(timing.part_nanos(2).unwrap().round(), 3000_f64);
+        }
+
+        #[test]
+        fn handles_missing_parts() {
+            let timing = Timing {
+                id: puzzle!(2024, 1),
+                part_1: None,
+                part_2: "n/a".parse().ok(),
+                total_nanos: 0_f64,
+            };
+
+            assert!(timing.part_nanos(1).is_none());
+            assert!(timing.part_nanos(2).is_none());
+            assert!(timing.part_nanos(3).is_none());
         }
     }
 
     mod merge {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -330,7 +572,7 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    id: puzzle!(2024, 3),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
@@ -338,10 +580,10 @@ mod tests {
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].id, puzzle!(2024, 1));
+            assert_eq!(merged.data[1].id, puzzle!(2024, 2));
+            assert_eq!(merged.data[2].id, puzzle!(2024, 3));
+            assert_eq!(merged.data[3].id, puzzle!(2024, 4));
         }
 
         #[test]
@@ -350,7 +592,7 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    id: puzzle!(2024, 2),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
@@ -359,10 +601,27 @@ mod tests {
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].id, puzzle!(2024, 1));
+            assert_eq!(merged.data[1].id, puzzle!(2024, 2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].id, puzzle!(2024, 4));
+        }
+
+        #[test]
+        fn handles_same_day_in_different_years() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    id: puzzle!(2023, 2),
+                    part_1: None,
+                    part_2: None,
+                    total_nanos: 0_f64,
+                }],
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].id, puzzle!(2023, 2));
+            assert_eq!(merged.data[2].id, puzzle!(2024, 2));
         }
 
         #[test]
@@ -0,0 +1,241 @@
+/// Module that polls the files a day depends on, so that `cargo solve --watch` can re-run it on changes.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::{
+    PuzzleId,
+    examples::{Expectations, examples},
+    run_multi::get_path_for_bin,
+    runner::{PARSE_STEP, PartResult},
+};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of the files a day depends on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Take a snapshot of the files a day depends on: its binary, its examples and the library.
+    #[must_use]
+    pub fn take(id: PuzzleId) -> Self {
+        let mut files = BTreeMap::new();
+
+        for path in watched_paths(id) {
+            add_modified(&mut files, &path);
+        }
+
+        Snapshot(files)
+    }
+
+    /// Poll the files of a day until they differ from this snapshot, and return the new snapshot.
+    #[must_use]
+    pub fn wait_for_change(&self, id: PuzzleId) -> Self {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let snapshot = Snapshot::take(id);
+            if snapshot != *self {
+                return snapshot;
+            }
+        }
+    }
+
+    /// The files that were changed, created or removed since a previous snapshot.
+    #[must_use]
+    pub fn changed_since<'a>(&'a self, previous: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changed: Vec<&Path> = self
+            .0
+            .iter()
+            .filter(|(path, modified)| previous.0.get(*path) != Some(*modified))
+            .map(|(path, _)| path.as_path())
+            .collect();
+
+        changed.extend(
+            previous
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .map(PathBuf::as_path),
+        );
+
+        changed.sort_unstable();
+        changed
+    }
+}
+
+/// The files and directories a day depends on. Directories are watched recursively.
+fn watched_paths(id: PuzzleId) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = [
+        "src/lib.rs",
+        "src/helpers.rs",
+        "src/helpers",
+        "src/custom_iter_tools.rs",
+        "src/custom_iter_tools",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+
+    paths.push(PathBuf::from(get_path_for_bin(id)));
+    paths.push(Expectations::path(id));
+    // NOTE: examples are listed on every poll, so that new example files count as a change.
+    paths.extend(examples(id).unwrap_or_default().into_iter().map(|x| x.path));
+
+    paths
+}
+
+/// Record the modification time of a file, or of every file below a directory. Missing paths are skipped.
+fn add_modified(files: &mut BTreeMap<PathBuf, SystemTime>, path: &Path) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            add_modified(files, &entry.path());
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        files.insert(path.to_path_buf(), modified);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The answer of a part in a run, compared to the previous run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswerChange {
+    pub part: u8,
+    /// The answer of the previous run, or its status if it had none. `None` if the part did not run.
+    pub previous: Option<String>,
+    /// The answer of this run, or its status if it has none.
+    pub current: String,
+}
+
+impl AnswerChange {
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.previous.as_ref() != Some(&self.current)
+    }
+}
+
+/// Compare the answers of every part of a run to the answers of the previous run.
+#[must_use]
+pub fn diff_answers(previous: &[PartResult], current: &[PartResult]) -> Vec<AnswerChange> {
+    current
+        .iter()
+        .filter(|result| result.part != PARSE_STEP)
+        .map(|result| AnswerChange {
+            part: result.part,
+            previous: previous
+                .iter()
+                .find(|x| x.part == result.part)
+                .map(describe),
+            current: describe(result),
+        })
+        .collect()
+}
+
+/// The answer of a part, or the reason it has none.
+fn describe(result: &PartResult) -> String {
+    result
+        .answer
+        .clone()
+        .unwrap_or_else(|| result.status.name().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+    use std::time::{Duration, SystemTime};
+
+    use super::{AnswerChange, Snapshot, diff_answers};
+    use crate::{
+        puzzle,
+        template::runner::{PartResult, Stats, Status},
+    };
+
+    fn result(part: u8, answer: Option<&str>, status: Status) -> PartResult {
+        let stats = Stats::from_samples(&[Duration::from_nanos(10)]);
+        PartResult::new(
+            puzzle!(2024, 1),
+            part,
+            answer.map(Into::into),
+            status,
+            stats,
+        )
+    }
+
+    #[test]
+    fn diffs_answers_of_runs() {
+        let previous = [
+            result(0, None, Status::Ok),
+            result(1, Some("11"), Status::Ok),
+            result(2, None, Status::NotImplemented),
+        ];
+        let current = [
+            result(0, None, Status::Ok),
+            result(1, Some("11"), Status::Ok),
+            result(2, Some("31"), Status::Ok),
+        ];
+
+        let changes = diff_answers(&previous, &current);
+
+        assert_eq!(
+            changes,
+            vec![
+                AnswerChange {
+                    part: 1,
+                    previous: Some("11".into()),
+                    current: "11".into(),
+                },
+                AnswerChange {
+                    part: 2,
+                    previous: Some("not implemented".into()),
+                    current: "31".into(),
+                },
+            ]
+        );
+        assert!(!changes[0].is_changed());
+        assert!(changes[1].is_changed());
+    }
+
+    #[test]
+    fn diffs_parts_that_did_not_run_before() {
+        let changes = diff_answers(&[], &[result(1, None, Status::Panicked("oops".into()))]);
+
+        assert_eq!(changes[0].previous, None);
+        assert_eq!(changes[0].current, "panicked");
+        assert!(changes[0].is_changed());
+    }
+
+    #[test]
+    fn lists_changed_files() {
+        let time = SystemTime::UNIX_EPOCH;
+        let later = time + Duration::from_secs(1);
+
+        let previous = Snapshot(
+            [("a.rs", time), ("b.rs", time), ("c.rs", time)]
+                .into_iter()
+                .map(|(path, time)| (path.into(), time))
+                .collect(),
+        );
+        let current = Snapshot(
+            [("a.rs", time), ("b.rs", later), ("d.rs", time)]
+                .into_iter()
+                .map(|(path, time)| (path.into(), time))
+                .collect(),
+        );
+
+        assert_eq!(
+            current.changed_since(&previous),
+            vec![Path::new("b.rs"), Path::new("c.rs"), Path::new("d.rs")]
+        );
+        assert!(previous.changed_since(&previous).is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The first year advent of code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with the shortened, 12-day puzzle calendar.
+const FIRST_SHORT_CALENDAR_YEAR: u16 = 2025;
+
+/// A valid year of advent (i.e. an integer of 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The number of puzzles in this year's calendar: 25 until 2024, 12 since 2025.
+    pub const fn days(self) -> u8 {
+        if self.0 >= FIRST_SHORT_CALENDAR_YEAR {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl PartialOrd<u16> for Year {
+    fn partial_cmp(&self, other: &u16) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value of 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_valid_years() {
+        assert_eq!("2015".parse::<Year>().unwrap(), 2015);
+        assert_eq!("2024".parse::<Year>().unwrap(), 2024);
+    }
+
+    #[test]
+    fn knows_calendar_length() {
+        assert_eq!(Year::new(2015).unwrap().days(), 25);
+        assert_eq!(Year::new(2024).unwrap().days(), 25);
+        assert_eq!(Year::new(2025).unwrap().days(), 12);
+    }
+
+    #[test]
+    fn rejects_invalid_years() {
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+        assert!("twenty".parse::<Year>().is_err());
+    }
+}